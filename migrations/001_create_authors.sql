@@ -0,0 +1,56 @@
+-- Authors become first-class records instead of free-text strings on `quotes`.
+
+CREATE TABLE IF NOT EXISTS authors (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE,
+    era VARCHAR,
+    birth_date DATE,
+    death_date DATE,
+    bio TEXT
+);
+
+CREATE TABLE IF NOT EXISTS author_names (
+    author_id INTEGER NOT NULL REFERENCES authors (id) ON DELETE CASCADE,
+    language VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    PRIMARY KEY (author_id, language)
+);
+
+CREATE TABLE IF NOT EXISTS author_aliases (
+    id SERIAL PRIMARY KEY,
+    author_id INTEGER NOT NULL REFERENCES authors (id) ON DELETE CASCADE,
+    alias VARCHAR NOT NULL UNIQUE
+);
+
+ALTER TABLE quotes
+    ADD COLUMN IF NOT EXISTS author_id INTEGER REFERENCES authors (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS quotes_author_id_idx ON quotes (author_id);
+
+-- Backfill: every distinct spelling becomes an author and an alias of itself.
+-- Spellings that refer to the same person (e.g. "Imam Ghazali" and "Al-Ghazali")
+-- are listed by the 017_author_merge_candidates.sql view for an admin to merge.
+INSERT INTO authors (name)
+SELECT DISTINCT btrim(author)
+FROM quotes
+WHERE btrim(author) <> ''
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO author_aliases (author_id, alias)
+SELECT DISTINCT a.id, btrim(q.author)
+FROM quotes q
+JOIN authors a ON a.name = btrim(q.author)
+ON CONFLICT (alias) DO NOTHING;
+
+UPDATE quotes q
+SET author_id = al.author_id
+FROM author_aliases al
+WHERE al.alias = btrim(q.author)
+  AND q.author_id IS DISTINCT FROM al.author_id;
+
+INSERT INTO author_names (author_id, language, name)
+SELECT DISTINCT ON (q.author_id, q.language) q.author_id, q.language, btrim(q.author)
+FROM quotes q
+WHERE q.author_id IS NOT NULL
+ORDER BY q.author_id, q.language, q.id
+ON CONFLICT (author_id, language) DO NOTHING;
@@ -0,0 +1,41 @@
+-- Structured bibliographic sources, linked to quotes through quote_source so a
+-- quote can cite more than one work. `quotes.reference` stays as the legacy
+-- free-text rendering.
+
+CREATE TABLE IF NOT EXISTS sources (
+    id SERIAL PRIMARY KEY,
+    kind VARCHAR NOT NULL DEFAULT 'other',
+    title TEXT NOT NULL,
+    volume VARCHAR,
+    chapter VARCHAR,
+    page VARCHAR,
+    collection VARCHAR,
+    number VARCHAR,
+    edition VARCHAR,
+    publisher VARCHAR,
+    year INTEGER,
+    url TEXT
+);
+
+CREATE TABLE IF NOT EXISTS quote_source (
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    source_id INTEGER NOT NULL REFERENCES sources (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (quote_id, source_id)
+);
+
+-- Backfill: each distinct legacy reference becomes an unstructured source that
+-- curators can later split into volume, chapter, page and so on.
+INSERT INTO sources (kind, title)
+SELECT DISTINCT 'other', btrim(reference)
+FROM quotes
+WHERE reference IS NOT NULL
+  AND btrim(reference) <> ''
+  AND NOT EXISTS (SELECT 1 FROM sources s WHERE s.title = btrim(quotes.reference));
+
+INSERT INTO quote_source (quote_id, source_id, position)
+SELECT q.id, MIN(s.id), 0
+FROM quotes q
+JOIN sources s ON s.title = btrim(q.reference) AND s.kind = 'other'
+GROUP BY q.id
+ON CONFLICT (quote_id, source_id) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Provenance grading for quotes. Random and QOTD selection can require a
+-- minimum grade (verified > weak > unknown > disputed > misattributed).
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'attribution_status') THEN
+        CREATE TYPE attribution_status AS ENUM (
+            'verified',
+            'weak',
+            'disputed',
+            'misattributed',
+            'unknown'
+        );
+    END IF;
+END
+$$;
+
+ALTER TABLE quotes
+    ADD COLUMN IF NOT EXISTS attribution_status attribution_status NOT NULL DEFAULT 'unknown',
+    ADD COLUMN IF NOT EXISTS grading_notes TEXT,
+    ADD COLUMN IF NOT EXISTS graded_by VARCHAR;
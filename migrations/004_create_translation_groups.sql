@@ -0,0 +1,15 @@
+-- Links the same saying across languages. Each quote belongs to at most one
+-- group, and a group holds at most one quote per language.
+
+CREATE TABLE IF NOT EXISTS translation_groups (
+    id SERIAL PRIMARY KEY,
+    original_quote_id INTEGER REFERENCES quotes (id) ON DELETE SET NULL
+);
+
+ALTER TABLE quotes
+    ADD COLUMN IF NOT EXISTS translation_group_id INTEGER
+        REFERENCES translation_groups (id) ON DELETE SET NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS quotes_translation_group_language_idx
+    ON quotes (translation_group_id, language)
+    WHERE translation_group_id IS NOT NULL;
@@ -0,0 +1,31 @@
+-- Language registry. Adding a language is now a data change: insert a row
+-- here and the /random_quote/{lang} and /qotd/{lang} routes pick it up.
+
+CREATE TABLE IF NOT EXISTS languages (
+    code VARCHAR PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    native_name VARCHAR NOT NULL,
+    script VARCHAR NOT NULL,
+    direction VARCHAR NOT NULL DEFAULT 'ltr' CHECK (direction IN ('ltr', 'rtl')),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    qotd_enabled BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+INSERT INTO languages (code, name, native_name, script, direction, enabled, qotd_enabled)
+VALUES
+    ('en', 'English', 'English', 'Latn', 'ltr', TRUE, TRUE),
+    ('ur-Latn', 'Roman Urdu', 'Roman Urdu', 'Latn', 'ltr', TRUE, TRUE),
+    ('ar', 'Arabic', 'العربية', 'Arab', 'rtl', TRUE, TRUE)
+ON CONFLICT (code) DO NOTHING;
+
+-- Any other code already present in quotes is registered disabled so the
+-- foreign key below can be validated; enable it once it has metadata.
+INSERT INTO languages (code, name, native_name, script, direction, enabled, qotd_enabled)
+SELECT DISTINCT language, language, language, 'Zyyy', 'ltr', FALSE, FALSE
+FROM quotes
+ON CONFLICT (code) DO NOTHING;
+
+ALTER TABLE quotes
+    DROP CONSTRAINT IF EXISTS quotes_language_fkey,
+    ADD CONSTRAINT quotes_language_fkey
+        FOREIGN KEY (language) REFERENCES languages (code) ON UPDATE CASCADE;
@@ -0,0 +1,7 @@
+-- Fallback chains for language negotiation. A request for a language with no
+-- quotes follows fallback_code links and finally lands on the default
+-- language (en).
+
+ALTER TABLE languages
+    ADD COLUMN IF NOT EXISTS fallback_code VARCHAR
+        REFERENCES languages (code) ON UPDATE CASCADE ON DELETE SET NULL;
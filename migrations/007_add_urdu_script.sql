@@ -0,0 +1,7 @@
+-- Urdu in native (Nastaliq) script. It falls back to Roman Urdu until quotes
+-- proposed through POST /admin/transliterate/seed_ur_arab have been reviewed
+-- and approved; enable the quote of the day once that is done.
+
+INSERT INTO languages (code, name, native_name, script, direction, enabled, qotd_enabled, fallback_code)
+VALUES ('ur-Arab', 'Urdu', 'اردو', 'Arab', 'rtl', TRUE, FALSE, 'ur-Latn')
+ON CONFLICT (code) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Normalised copy of the quote text used by GET /quotes/search. Mirrors
+-- helper::arabic::normalize: Arabic diacritics and tatweel removed, alef,
+-- yaa, taa marbuta and hamza carriers folded, lowercased, whitespace collapsed.
+
+ALTER TABLE quotes
+    ADD COLUMN IF NOT EXISTS search_text TEXT GENERATED ALWAYS AS (
+        lower(
+            btrim(
+                regexp_replace(
+                    translate(
+                        regexp_replace(quote, '[\u0610-\u061A\u064B-\u065F\u0670\u06D6-\u06ED\u0640]', '', 'g'),
+                        'أإآٱىةؤئ',
+                        'اااايهوي'
+                    ),
+                    '\s+',
+                    ' ',
+                    'g'
+                )
+            )
+        )
+    ) STORED;
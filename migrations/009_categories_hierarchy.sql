@@ -0,0 +1,37 @@
+-- Categories gain a URL slug, an optional parent for hierarchies such as
+-- "Ethics > Patience", and per-language display names.
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS slug TEXT;
+
+UPDATE categories
+SET slug = btrim(regexp_replace(lower(name), '[^a-z0-9]+', '-', 'g'), '-') || '-' || id
+WHERE slug IS NULL;
+
+UPDATE categories c
+SET slug = btrim(regexp_replace(lower(c.name), '[^a-z0-9]+', '-', 'g'), '-')
+WHERE btrim(regexp_replace(lower(c.name), '[^a-z0-9]+', '-', 'g'), '-') <> ''
+  AND NOT EXISTS (
+      SELECT 1 FROM categories other
+      WHERE other.id <> c.id
+        AND btrim(regexp_replace(lower(other.name), '[^a-z0-9]+', '-', 'g'), '-')
+            = btrim(regexp_replace(lower(c.name), '[^a-z0-9]+', '-', 'g'), '-')
+  );
+
+ALTER TABLE categories ALTER COLUMN slug SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS categories_slug_idx ON categories (slug);
+
+ALTER TABLE categories
+    ADD COLUMN IF NOT EXISTS parent_id INTEGER
+        REFERENCES categories (id) ON DELETE SET NULL;
+
+CREATE TABLE IF NOT EXISTS category_names (
+    category_id INTEGER NOT NULL REFERENCES categories (id) ON DELETE CASCADE,
+    language TEXT NOT NULL REFERENCES languages (code),
+    name TEXT NOT NULL,
+    PRIMARY KEY (category_id, language)
+);
+
+INSERT INTO category_names (category_id, language, name)
+SELECT id, 'en', name FROM categories
+ON CONFLICT DO NOTHING;
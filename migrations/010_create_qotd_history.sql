@@ -0,0 +1,15 @@
+-- One row per language and day a quote was featured, so exports can carry the
+-- quote of the day history. Days follow the Europe/Berlin rollover.
+
+CREATE TABLE IF NOT EXISTS qotd_history (
+    id SERIAL PRIMARY KEY,
+    language VARCHAR NOT NULL REFERENCES languages (code),
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    featured_on DATE NOT NULL,
+    UNIQUE (language, featured_on)
+);
+
+INSERT INTO qotd_history (language, quote_id, featured_on)
+SELECT language, quote_id, (now() AT TIME ZONE 'Europe/Berlin')::date
+FROM quote_of_the_day
+ON CONFLICT (language, featured_on) DO NOTHING;
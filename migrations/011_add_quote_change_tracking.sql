@@ -0,0 +1,49 @@
+-- Change tracking for delta sync. Every insert and every update that touches
+-- a quote's content takes the next value of quote_change_seq, which
+-- GET /changes uses as its cursor. Deleted quotes keep their row, with
+-- deleted_at set, so the feed can report a tombstone.
+
+ALTER TABLE quotes
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS change_seq BIGINT;
+
+CREATE SEQUENCE IF NOT EXISTS quote_change_seq;
+
+UPDATE quotes q
+SET change_seq = numbered.seq
+FROM (
+    SELECT id, nextval('quote_change_seq') AS seq
+    FROM (SELECT id FROM quotes WHERE change_seq IS NULL ORDER BY id) ordered
+) numbered
+WHERE q.id = numbered.id;
+
+ALTER TABLE quotes
+    ALTER COLUMN change_seq SET DEFAULT nextval('quote_change_seq'),
+    ALTER COLUMN change_seq SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS quotes_change_seq_idx ON quotes (change_seq);
+
+CREATE OR REPLACE FUNCTION quotes_track_change() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at := now();
+    NEW.change_seq := nextval('quote_change_seq');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS quotes_track_change ON quotes;
+CREATE TRIGGER quotes_track_change
+    BEFORE UPDATE ON quotes
+    FOR EACH ROW
+    WHEN (
+        (OLD.quote, OLD.author, OLD.author_id, OLD.reference, OLD.language,
+         OLD.attribution_status, OLD.grading_notes, OLD.graded_by,
+         OLD.translation_group_id, OLD.deleted_at)
+        IS DISTINCT FROM
+        (NEW.quote, NEW.author, NEW.author_id, NEW.reference, NEW.language,
+         NEW.attribution_status, NEW.grading_notes, NEW.graded_by,
+         NEW.translation_group_id, NEW.deleted_at)
+    )
+    EXECUTE FUNCTION quotes_track_change();
@@ -0,0 +1,39 @@
+-- Every admin change to a quote stores a snapshot of its text, author,
+-- reference and categories afterwards, with the API key that made it, so a
+-- quote can be audited and reverted. Existing quotes start at revision 1.
+
+CREATE TABLE IF NOT EXISTS quote_revisions (
+    id SERIAL PRIMARY KEY,
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    action VARCHAR NOT NULL
+        CHECK (action IN ('create', 'update', 'delete', 'restore', 'revert')),
+    content TEXT NOT NULL,
+    author VARCHAR NOT NULL,
+    author_id INTEGER,
+    reference TEXT,
+    category_ids INTEGER[] NOT NULL DEFAULT '{}',
+    changed_by VARCHAR,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (quote_id, revision)
+);
+
+INSERT INTO quote_revisions
+    (quote_id, revision, action, content, author, author_id, reference, category_ids, changed_at)
+SELECT
+    q.id,
+    1,
+    'create',
+    q.quote,
+    q.author,
+    q.author_id,
+    q.reference,
+    COALESCE(
+        (SELECT array_agg(qc.category_id ORDER BY qc.category_id)
+         FROM quote_category qc
+         WHERE qc.quote_id = q.id),
+        '{}'
+    ),
+    q.created_at
+FROM quotes q
+WHERE NOT EXISTS (SELECT 1 FROM quote_revisions r WHERE r.quote_id = q.id);
@@ -0,0 +1,27 @@
+-- Quotes proposed by API key holders. They wait as 'pending' until an admin
+-- approves them, which creates the quote and links it through quote_id, or
+-- rejects them with a reason.
+
+CREATE TABLE IF NOT EXISTS quote_submissions (
+    id SERIAL PRIMARY KEY,
+    submitted_by VARCHAR NOT NULL,
+    content TEXT NOT NULL,
+    author VARCHAR NOT NULL,
+    reference TEXT,
+    language VARCHAR NOT NULL REFERENCES languages (code),
+    category_ids INTEGER[] NOT NULL DEFAULT '{}',
+    notes TEXT,
+    status VARCHAR NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'rejected')),
+    rejection_reason TEXT,
+    quote_id INTEGER REFERENCES quotes (id) ON DELETE SET NULL,
+    reviewed_by VARCHAR,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS quote_submissions_submitted_by_idx
+    ON quote_submissions (submitted_by, id);
+CREATE INDEX IF NOT EXISTS quote_submissions_status_idx
+    ON quote_submissions (status, id);
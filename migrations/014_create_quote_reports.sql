@@ -0,0 +1,22 @@
+-- Corrections reported by API key holders against live quotes. Admins work
+-- the 'open' queue and either resolve a report, usually together with an
+-- edit whose revision is kept on the report, or dismiss it.
+
+CREATE TABLE IF NOT EXISTS quote_reports (
+    id SERIAL PRIMARY KEY,
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    reason VARCHAR NOT NULL
+        CHECK (reason IN ('misattribution', 'typo', 'wrong_reference', 'offensive')),
+    details TEXT,
+    reported_by VARCHAR NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'resolved', 'dismissed')),
+    resolution TEXT,
+    revision INTEGER,
+    resolved_by VARCHAR,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS quote_reports_status_idx ON quote_reports (status, id);
+CREATE INDEX IF NOT EXISTS quote_reports_quote_id_idx ON quote_reports (quote_id);
@@ -0,0 +1,13 @@
+-- Quotes saved per API key. Integrating apps can keep favourites for each of
+-- their own users by passing an end-user id; '' is the key's own list.
+
+CREATE TABLE IF NOT EXISTS favorites (
+    key_id VARCHAR NOT NULL,
+    end_user_id VARCHAR NOT NULL DEFAULT '',
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (key_id, end_user_id, quote_id)
+);
+
+CREATE INDEX IF NOT EXISTS favorites_owner_created_idx
+    ON favorites (key_id, end_user_id, created_at DESC);
@@ -0,0 +1,13 @@
+-- One 1-5 rating per API key and quote. Popularity is aggregated from these
+-- rows at query time; updated_at lets GET /quotes/popular look at a period.
+
+CREATE TABLE IF NOT EXISTS quote_ratings (
+    key_id VARCHAR NOT NULL,
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value BETWEEN 1 AND 5),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (key_id, quote_id)
+);
+
+CREATE INDEX IF NOT EXISTS quote_ratings_quote_id_idx ON quote_ratings (quote_id, updated_at);
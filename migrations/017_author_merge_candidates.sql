@@ -0,0 +1,30 @@
+-- The 001 backfill turned every distinct spelling into its own author, so
+-- "Imam Ghazali" and "Al-Ghazali" are still two people. This view lists the
+-- authors whose names share a key once case, punctuation, a leading honorific
+-- and an "al-" article are dropped.
+--
+-- The key also matches distinct people ("Imam Malik" and "Malik"), so
+-- nothing is merged here. Admins review the groups through
+-- GET /admin/authors/merge_candidates and merge the right ones one at a time
+-- with POST /admin/authors/{id}/merge.
+
+CREATE OR REPLACE FUNCTION author_merge_key(name TEXT) RETURNS TEXT AS $$
+    SELECT regexp_replace(
+        regexp_replace(
+            regexp_replace(
+                lower(btrim(name)),
+                '^((imam|shaykh|sheikh|shaikh|hazrat|sayyid|syed|mawlana|maulana)\.?\s+)+', ''),
+            '^(al|el)[-\s]+', ''),
+        '[^[:alnum:]]+', '', 'g');
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE VIEW author_merge_candidates AS
+SELECT author_id, name, merge_key
+FROM (
+    SELECT id AS author_id,
+           name,
+           author_merge_key(name) AS merge_key,
+           count(*) OVER (PARTITION BY author_merge_key(name)) AS group_size
+    FROM authors
+) keyed
+WHERE merge_key <> '' AND group_size > 1;
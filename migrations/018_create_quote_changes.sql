@@ -0,0 +1,144 @@
+-- Commit-ordered change log behind GET /changes and the bundle versions.
+--
+-- quote_change_seq values were taken when a row was written, so two
+-- overlapping transactions could commit out of order, and a client whose
+-- cursor had passed the higher value never saw the lower one. Every writer
+-- now takes a transaction-level advisory lock before drawing a value, which
+-- holds other writers off until it commits: cursor order is commit order.
+--
+-- quote_changes keeps the latest change per quote and language. A quote that
+-- moves to another language leaves a deletion in the old one, and adding or
+-- removing a quote's categories or sources counts as a change to the quote.
+
+CREATE TABLE IF NOT EXISTS quote_changes (
+    quote_id INTEGER NOT NULL REFERENCES quotes (id) ON DELETE CASCADE,
+    language VARCHAR NOT NULL,
+    seq BIGINT NOT NULL,
+    deleted BOOLEAN NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (quote_id, language)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS quote_changes_seq_idx ON quote_changes (seq);
+CREATE INDEX IF NOT EXISTS quote_changes_language_seq_idx ON quote_changes (language, seq);
+
+INSERT INTO quote_changes (quote_id, language, seq, deleted, changed_at)
+SELECT id, language, change_seq, deleted_at IS NOT NULL, COALESCE(deleted_at, updated_at)
+FROM quotes
+ON CONFLICT (quote_id, language) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION next_quote_change_seq() RETURNS BIGINT AS $$
+BEGIN
+    -- Arbitrary key shared by every writer of quote_change_seq.
+    PERFORM pg_advisory_xact_lock(7283160421);
+    RETURN nextval('quote_change_seq');
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION log_quote_change(
+    p_quote_id INTEGER,
+    p_language VARCHAR,
+    p_deleted BOOLEAN,
+    p_seq BIGINT
+) RETURNS void AS $$
+BEGIN
+    INSERT INTO quote_changes (quote_id, language, seq, deleted, changed_at)
+    VALUES (p_quote_id, p_language, p_seq, p_deleted, now())
+    ON CONFLICT (quote_id, language) DO UPDATE
+        SET seq = EXCLUDED.seq,
+            deleted = EXCLUDED.deleted,
+            changed_at = EXCLUDED.changed_at;
+END;
+$$ LANGUAGE plpgsql;
+
+-- The log row references the quote, so it is written once the quote row
+-- exists, by the AFTER triggers below.
+ALTER TABLE quotes ALTER COLUMN change_seq DROP DEFAULT;
+
+CREATE OR REPLACE FUNCTION quotes_track_change() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at := now();
+    NEW.change_seq := next_quote_change_seq();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION quotes_log_change() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND OLD.language IS DISTINCT FROM NEW.language THEN
+        PERFORM log_quote_change(OLD.id, OLD.language, TRUE, next_quote_change_seq());
+    END IF;
+    PERFORM log_quote_change(NEW.id, NEW.language, NEW.deleted_at IS NOT NULL, NEW.change_seq);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS quotes_track_insert ON quotes;
+CREATE TRIGGER quotes_track_insert
+    BEFORE INSERT ON quotes
+    FOR EACH ROW
+    EXECUTE FUNCTION quotes_track_change();
+
+DROP TRIGGER IF EXISTS quotes_log_insert ON quotes;
+CREATE TRIGGER quotes_log_insert
+    AFTER INSERT ON quotes
+    FOR EACH ROW
+    EXECUTE FUNCTION quotes_log_change();
+
+DROP TRIGGER IF EXISTS quotes_log_update ON quotes;
+CREATE TRIGGER quotes_log_update
+    AFTER UPDATE ON quotes
+    FOR EACH ROW
+    WHEN (
+        (OLD.quote, OLD.author, OLD.author_id, OLD.reference, OLD.language,
+         OLD.attribution_status, OLD.grading_notes, OLD.graded_by,
+         OLD.translation_group_id, OLD.deleted_at)
+        IS DISTINCT FROM
+        (NEW.quote, NEW.author, NEW.author_id, NEW.reference, NEW.language,
+         NEW.attribution_status, NEW.grading_notes, NEW.graded_by,
+         NEW.translation_group_id, NEW.deleted_at)
+    )
+    EXECUTE FUNCTION quotes_log_change();
+
+-- Bumps a quote whose categories or sources changed. Only change_seq and
+-- updated_at are written, so the quote triggers above stay quiet.
+CREATE OR REPLACE FUNCTION touch_quote(p_quote_id INTEGER) RETURNS void AS $$
+DECLARE
+    touched quotes%ROWTYPE;
+BEGIN
+    UPDATE quotes
+    SET updated_at = now(),
+        change_seq = next_quote_change_seq()
+    WHERE id = p_quote_id
+    RETURNING * INTO touched;
+
+    IF FOUND THEN
+        PERFORM log_quote_change(
+            touched.id, touched.language, touched.deleted_at IS NOT NULL, touched.change_seq);
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION quote_links_touch_quote() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM touch_quote(OLD.quote_id);
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.quote_id <> OLD.quote_id) THEN
+        PERFORM touch_quote(NEW.quote_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS quote_category_touch_quote ON quote_category;
+CREATE TRIGGER quote_category_touch_quote
+    AFTER INSERT OR UPDATE OR DELETE ON quote_category
+    FOR EACH ROW
+    EXECUTE FUNCTION quote_links_touch_quote();
+
+DROP TRIGGER IF EXISTS quote_source_touch_quote ON quote_source;
+CREATE TRIGGER quote_source_touch_quote
+    AFTER INSERT OR UPDATE OR DELETE ON quote_source
+    FOR EACH ROW
+    EXECUTE FUNCTION quote_links_touch_quote();
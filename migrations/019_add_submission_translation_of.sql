@@ -0,0 +1,10 @@
+-- Machine transliterations are proposed as submissions instead of being
+-- published. translation_of points at the quote a submission translates, so
+-- approving it joins that quote's translation group.
+
+ALTER TABLE quote_submissions
+    ADD COLUMN IF NOT EXISTS translation_of INTEGER REFERENCES quotes (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS quote_submissions_translation_of_idx
+    ON quote_submissions (translation_of)
+    WHERE translation_of IS NOT NULL;
@@ -0,0 +1,26 @@
+-- One open report per quote and key, enforced by the database so two
+-- concurrent reports cannot both pass the check in `report_quote`. Duplicate
+-- open reports left over from before are dismissed in favour of the oldest.
+
+UPDATE quote_reports AS duplicate
+SET status = 'dismissed',
+    resolution = 'Duplicate of report ' || original.id,
+    resolved_at = now()
+FROM quote_reports AS original
+WHERE duplicate.status = 'open'
+  AND original.status = 'open'
+  AND original.quote_id = duplicate.quote_id
+  AND original.reported_by = duplicate.reported_by
+  AND original.id < duplicate.id
+  AND NOT EXISTS (
+      SELECT 1
+      FROM quote_reports AS earlier
+      WHERE earlier.status = 'open'
+        AND earlier.quote_id = duplicate.quote_id
+        AND earlier.reported_by = duplicate.reported_by
+        AND earlier.id < original.id
+  );
+
+CREATE UNIQUE INDEX IF NOT EXISTS quote_reports_open_idx
+    ON quote_reports (quote_id, reported_by)
+    WHERE status = 'open';
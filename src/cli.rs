@@ -0,0 +1,92 @@
+use crate::db;
+use crate::helper::export::{self, ExportFormat};
+use crate::helper::import::{self, ImportFormat};
+use crate::models::errors::AppError;
+
+use futures_util::StreamExt;
+use std::io::Write;
+use std::sync::Arc;
+
+const USAGE: &str = "Usage:
+  scholarly_quotes import <file> [--format csv|json|ndjson|yaml] [--dry-run]
+  scholarly_quotes export [--format json|ndjson|csv] [--output <file>]";
+
+/// Runs an offline command instead of the HTTP server. Only the database is
+/// needed, so Redis and Unkey do not have to be reachable.
+pub async fn run(args: &[String]) -> Result<(), AppError> {
+    match args.first().map(String::as_str) {
+        Some("import") => run_import(&args[1..]).await,
+        Some("export") => run_export(&args[1..]).await,
+        _ => Err(AppError::BadRequest(USAGE.to_string())),
+    }
+}
+
+async fn run_import(args: &[String]) -> Result<(), AppError> {
+    let mut path = None;
+    let mut format = None;
+    let mut dry_run = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| AppError::BadRequest(USAGE.to_string()))?;
+                format = Some(value.parse::<ImportFormat>()?);
+            }
+            value if path.is_none() => path = Some(value.to_string()),
+            _ => return Err(AppError::BadRequest(USAGE.to_string())),
+        }
+    }
+
+    let path = path.ok_or_else(|| AppError::BadRequest(USAGE.to_string()))?;
+    let format = format
+        .or_else(|| ImportFormat::from_path(&path))
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Cannot tell the format of '{}', pass --format",
+                path
+            ))
+        })?;
+
+    let body = std::fs::read(&path)?;
+    let db = db::conn::setup_db().await?;
+    let report =
+        import::import_quotes(&db, import::parse_records(format, &body)?, dry_run, None).await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+async fn run_export(args: &[String]) -> Result<(), AppError> {
+    let mut format = ExportFormat::Json;
+    let mut output = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| AppError::BadRequest(USAGE.to_string()));
+        match arg.as_str() {
+            "--format" => format = value?.parse()?,
+            "--output" => output = Some(value?.to_string()),
+            _ => return Err(AppError::BadRequest(USAGE.to_string())),
+        }
+    }
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    let db = Arc::new(db::conn::setup_db().await?);
+    let mut chunks = Box::pin(export::export_stream(db, format));
+    while let Some(chunk) = chunks.next().await {
+        writer.write_all(&chunk?)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
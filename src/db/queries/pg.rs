@@ -1,22 +1,58 @@
-use crate::entities::quotes::{Column, Entity as QuoteEntity};
-use crate::models::data::ResponseQuote;
+pub mod admin_quotes;
+pub mod authors;
+pub mod categories;
+pub mod changes;
+pub mod export;
+pub mod favorites;
+pub mod import;
+pub mod languages;
+pub mod lint;
+pub mod ratings;
+pub mod reports;
+pub mod revisions;
+pub mod sources;
+pub mod submissions;
+pub mod translations;
+
+use crate::entities::quotes::{self, Column, Entity as QuoteEntity};
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::helper::attribution;
+use crate::models::data::{PageQuery, PagedResponse, ResponseQuote};
 use crate::models::errors::AppError;
+use chrono::Utc;
+use chrono_tz::Europe::Berlin;
 use log::info;
 use rand::Rng;
 use sea_orm::ColumnTrait;
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
 
+use crate::entities::qotd_history::{self, Entity as QotdHistoryEntity};
 use crate::entities::quote_of_the_day::{self, Column as QOTDColumn, Entity as QOTDEntity};
 
 use sea_orm::sea_query::OnConflict;
 use sea_orm::ActiveValue::Set;
 
-pub async fn fetch_ids_by_language(
-    db: &DatabaseConnection,
+/// Quotes that have not been deleted. Every public read starts here.
+pub fn find_live_quotes() -> Select<QuoteEntity> {
+    QuoteEntity::find().filter(Column::DeletedAt.is_null())
+}
+
+pub async fn fetch_ids_by_language<C: ConnectionTrait>(
+    db: &C,
     language: &str,
+    min_grade: Option<&AttributionStatus>,
 ) -> Result<Vec<i32>, AppError> {
-    let quote_ids: Vec<i32> = QuoteEntity::find()
-        .filter(Column::Language.eq(language))
+    let mut query = find_live_quotes().filter(Column::Language.eq(language));
+
+    if let Some(min_grade) = min_grade {
+        query = query
+            .filter(Column::AttributionStatus.is_in(attribution::statuses_at_least(min_grade)));
+    }
+
+    let quote_ids: Vec<i32> = query
         .column(Column::Id)
         .all(db)
         .await?
@@ -30,67 +66,182 @@ pub async fn fetch_ids_by_language(
 pub async fn fetch_random_quote_by_language(
     db: &DatabaseConnection,
     language: &str,
+    min_grade: Option<&AttributionStatus>,
 ) -> Result<ResponseQuote, AppError> {
-    let quote_ids = fetch_ids_by_language(db, language).await?;
+    let quote_ids = fetch_ids_by_language(db, language, min_grade).await?;
 
     match quote_ids.is_empty() {
         true => Err(AppError::NotFound(
             "No quotes found in the database.".to_string(),
         )),
         false => {
-            let random_id =
-                rand::thread_rng().gen_range(quote_ids[0]..=quote_ids[quote_ids.len() - 1]);
+            let random_id = quote_ids[rand::thread_rng().gen_range(0..quote_ids.len())];
 
             let random_quote = QuoteEntity::find_by_id(random_id)
                 .one(db)
                 .await?
                 .ok_or_else(|| AppError::NotFound("Quote Not Found in DB".to_string()))?;
             info!("{:?}", random_quote);
-            Ok(ResponseQuote {
-                id: random_quote.id,
-                content: random_quote.quote,
-                author: random_quote.author,
-                reference: random_quote.reference.expect("Category should not be None"),
-                language: random_quote.language,
-                ..Default::default()
-            })
+            to_response_quote(db, random_quote).await
         }
     }
 }
 
-#[warn(dead_code)]
-pub async fn insert_qotd_into_db(
+pub async fn to_response_quotes(
+    db: &DatabaseConnection,
+    quotes: Vec<quotes::Model>,
+) -> Result<Vec<ResponseQuote>, AppError> {
+    let ids: Vec<i32> = quotes.iter().map(|quote| quote.id).collect();
+    let mut references = sources::fetch_references_for_quotes(db, &ids).await?;
+
+    let mut codes: Vec<String> = quotes.iter().map(|quote| quote.language.clone()).collect();
+    codes.sort();
+    codes.dedup();
+    let directions = languages::fetch_directions(db, &codes).await?;
+
+    Ok(quotes
+        .into_iter()
+        .map(|quote| {
+            let references = references.remove(&quote.id).unwrap_or_default();
+            let reference = quote
+                .reference
+                .or_else(|| references.first().map(|reference| reference.to_string()))
+                .unwrap_or_else(|| "Unknown".to_string());
+
+            ResponseQuote {
+                id: quote.id,
+                content: quote.quote,
+                author: quote.author,
+                author_id: quote.author_id,
+                reference,
+                references,
+                direction: directions
+                    .get(&quote.language)
+                    .cloned()
+                    .unwrap_or_else(|| "ltr".to_string()),
+                language: quote.language,
+                attribution_status: quote.attribution_status,
+                grading_notes: quote.grading_notes,
+                graded_by: quote.graded_by,
+                translation_group_id: quote.translation_group_id,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+pub async fn to_response_quote(
+    db: &DatabaseConnection,
+    quote: quotes::Model,
+) -> Result<ResponseQuote, AppError> {
+    to_response_quotes(db, vec![quote])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound("Quote Not Found in DB".to_string()))
+}
+
+/// Live quotes among `quote_ids`, in no particular order.
+pub async fn fetch_quotes_by_ids(
+    db: &DatabaseConnection,
+    quote_ids: &[i32],
+) -> Result<Vec<ResponseQuote>, AppError> {
+    if quote_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let quotes = find_live_quotes()
+        .filter(Column::Id.is_in(quote_ids.iter().copied()))
+        .all(db)
+        .await?;
+    to_response_quotes(db, quotes).await
+}
+
+pub async fn fetch_quote_by_id(
+    db: &DatabaseConnection,
+    quote_id: i32,
+) -> Result<ResponseQuote, AppError> {
+    let quote = find_live_quotes()
+        .filter(Column::Id.eq(quote_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+
+    to_response_quote(db, quote).await
+}
+
+pub async fn search_quotes(
+    db: &DatabaseConnection,
+    normalized_query: &str,
+    language: Option<&str>,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    let pattern = format!(
+        "%{}%",
+        normalized_query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    let mut query = find_live_quotes().filter(Column::SearchText.like(pattern));
+    if let Some(language) = language {
+        query = query.filter(Column::Language.eq(language));
+    }
+
+    let paginator = query.order_by_asc(Column::Id).paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = to_response_quotes(db, paginator.fetch_page(page.page() - 1).await?).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+pub async fn update_qotd_in_db(
     db_conn: &DatabaseConnection,
     quote: &ResponseQuote,
 ) -> Result<(), AppError> {
-    let conflict = OnConflict::columns([QOTDColumn::Language])
-        .do_nothing()
-        .clone();
-    let _ = QOTDEntity::insert(quote_of_the_day::ActiveModel {
-        language: Set(quote.language.to_string()),
-        quote_id: Set(quote.id),
+    set_qotd(db_conn, &quote.language, quote.id).await
+}
+
+/// Features `quote_id` for `language` and records it in the QOTD history.
+pub async fn set_qotd<C: ConnectionTrait>(
+    db_conn: &C,
+    language: &str,
+    quote_id: i32,
+) -> Result<(), AppError> {
+    let conflict = OnConflict::column(QOTDColumn::Language)
+        .update_column(QOTDColumn::QuoteId)
+        .to_owned();
+
+    QOTDEntity::insert(quote_of_the_day::ActiveModel {
+        language: Set(language.to_string()),
+        quote_id: Set(quote_id),
         ..Default::default()
     })
     .on_conflict(conflict)
     .exec(db_conn)
     .await?;
 
-    Ok(())
-}
+    let history_conflict = OnConflict::columns([
+        qotd_history::Column::Language,
+        qotd_history::Column::FeaturedOn,
+    ])
+    .update_column(qotd_history::Column::QuoteId)
+    .to_owned();
 
-pub async fn update_qotd_in_db(
-    db_conn: &DatabaseConnection,
-    quote: &ResponseQuote,
-) -> Result<(), AppError> {
-    let _ = QOTDEntity::update_many()
-        .set(quote_of_the_day::ActiveModel {
-            language: Set(quote.language.to_string()),
-            quote_id: Set(quote.id),
-            ..Default::default()
-        })
-        .filter(quote_of_the_day::Column::Language.eq(quote.language.to_string()))
-        .exec(db_conn)
-        .await?;
+    QotdHistoryEntity::insert(qotd_history::ActiveModel {
+        language: Set(language.to_string()),
+        quote_id: Set(quote_id),
+        featured_on: Set(Utc::now().with_timezone(&Berlin).date_naive()),
+        ..Default::default()
+    })
+    .on_conflict(history_conflict)
+    .exec(db_conn)
+    .await?;
 
     Ok(())
 }
@@ -106,18 +257,7 @@ pub async fn get_qotd_from_db(
         .await?;
 
     match qotd {
-        Some((_, Some(quote))) => {
-            let response_quote = ResponseQuote {
-                id: quote.id,
-                content: quote.quote,
-                author: quote.author,
-                reference: quote.reference.unwrap_or_else(|| "Unknown".to_string()),
-                language: quote.language,
-                ..Default::default()
-            };
-
-            Ok(response_quote)
-        }
+        Some((_, Some(quote))) => to_response_quote(db_conn, quote).await,
         Some((_, None)) => Err(AppError::NotFound("No quote content available".to_string())),
         None => Err(AppError::NotFound(
             "No quote found for this language".to_string(),
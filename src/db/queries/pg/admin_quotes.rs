@@ -0,0 +1,232 @@
+use crate::entities::authors::Entity as AuthorEntity;
+use crate::entities::categories::{self, Entity as CategoryEntity};
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quote_of_the_day::{self, Entity as QOTDEntity};
+use crate::entities::quotes::{self, Entity as QuoteEntity};
+use crate::models::data::RevisionAction;
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, QueryFilter, TransactionTrait,
+};
+use std::collections::HashSet;
+
+use super::find_live_quotes;
+use super::revisions::record_revisions;
+
+pub async fn ensure_author_exists(db: &DatabaseConnection, author_id: i32) -> Result<(), AppError> {
+    AuthorEntity::find_by_id(author_id)
+        .one(db)
+        .await?
+        .map(|_| ())
+        .ok_or_else(|| AppError::BadRequest(format!("Author {} does not exist", author_id)))
+}
+
+pub async fn ensure_quotes_exist(
+    db: &DatabaseConnection,
+    quote_ids: &[i32],
+) -> Result<(), AppError> {
+    let found: HashSet<i32> = find_live_quotes()
+        .filter(quotes::Column::Id.is_in(quote_ids.iter().copied()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|quote| quote.id)
+        .collect();
+
+    let missing: Vec<String> = quote_ids
+        .iter()
+        .filter(|id| !found.contains(id))
+        .map(|id| id.to_string())
+        .collect();
+
+    match missing.is_empty() {
+        true => Ok(()),
+        false => Err(AppError::NotFound(format!(
+            "Quotes not found: {}",
+            missing.join(", ")
+        ))),
+    }
+}
+
+/// The ids in `category_ids` that still name a category.
+pub async fn fetch_existing_category_ids(
+    db: &DatabaseConnection,
+    category_ids: &[i32],
+) -> Result<Vec<i32>, AppError> {
+    if category_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(CategoryEntity::find()
+        .filter(categories::Column::Id.is_in(category_ids.iter().copied()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|category| category.id)
+        .collect())
+}
+
+pub async fn ensure_categories_exist(
+    db: &DatabaseConnection,
+    category_ids: &[i32],
+) -> Result<(), AppError> {
+    let found: HashSet<i32> = fetch_existing_category_ids(db, category_ids)
+        .await?
+        .into_iter()
+        .collect();
+
+    let missing: Vec<String> = category_ids
+        .iter()
+        .filter(|id| !found.contains(id))
+        .map(|id| id.to_string())
+        .collect();
+
+    match missing.is_empty() {
+        true => Ok(()),
+        false => Err(AppError::BadRequest(format!(
+            "Categories not found: {}",
+            missing.join(", ")
+        ))),
+    }
+}
+
+async fn replace_categories(
+    txn: &DatabaseTransaction,
+    quote_id: i32,
+    category_ids: &[i32],
+) -> Result<(), AppError> {
+    QuoteCategoryEntity::delete_many()
+        .filter(quote_category::Column::QuoteId.eq(quote_id))
+        .exec(txn)
+        .await?;
+
+    let mut seen = HashSet::new();
+    let links: Vec<quote_category::ActiveModel> = category_ids
+        .iter()
+        .filter(|id| seen.insert(**id))
+        .map(|id| quote_category::ActiveModel {
+            quote_id: Set(quote_id),
+            category_id: Set(*id),
+        })
+        .collect();
+
+    if !links.is_empty() {
+        QuoteCategoryEntity::insert_many(links).exec(txn).await?;
+    }
+
+    Ok(())
+}
+
+/// Inserts a quote with its categories and first revision inside `txn`, so
+/// callers can tie other writes to it.
+pub async fn insert_quote(
+    txn: &DatabaseTransaction,
+    quote: quotes::ActiveModel,
+    category_ids: &[i32],
+    changed_by: Option<&str>,
+) -> Result<quotes::Model, AppError> {
+    let quote = QuoteEntity::insert(quote).exec_with_returning(txn).await?;
+    replace_categories(txn, quote.id, category_ids).await?;
+    record_revisions(txn, &[quote.id], RevisionAction::Create, changed_by).await?;
+    Ok(quote)
+}
+
+/// Updates a quote and records the revision inside `txn`.
+pub async fn update_quote(
+    txn: &DatabaseTransaction,
+    quote: quotes::ActiveModel,
+    category_ids: Option<&[i32]>,
+    action: RevisionAction,
+    changed_by: Option<&str>,
+) -> Result<quotes::Model, AppError> {
+    let quote = quote.update(txn).await?;
+    if let Some(category_ids) = category_ids {
+        replace_categories(txn, quote.id, category_ids).await?;
+    }
+    record_revisions(txn, &[quote.id], action, changed_by).await?;
+    Ok(quote)
+}
+
+/// Marks the quote deleted inside `txn`, keeping the row so the change feed
+/// can report a tombstone.
+pub async fn delete_quote(
+    txn: &DatabaseTransaction,
+    quote_id: i32,
+    changed_by: Option<&str>,
+) -> Result<(), AppError> {
+    let result = QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            deleted_at: Set(Some(Utc::now().into())),
+            ..Default::default()
+        })
+        .filter(quotes::Column::Id.eq(quote_id))
+        .filter(quotes::Column::DeletedAt.is_null())
+        .exec(txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!("Quote {} not found", quote_id)));
+    }
+    record_revisions(txn, &[quote_id], RevisionAction::Delete, changed_by).await
+}
+
+/// Clears `deleted_at` on a soft-deleted quote.
+pub async fn restore_quote(
+    db: &DatabaseConnection,
+    quote_id: i32,
+    changed_by: Option<&str>,
+) -> Result<quotes::Model, AppError> {
+    let txn = db.begin().await?;
+
+    let result = QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            deleted_at: Set(None),
+            ..Default::default()
+        })
+        .filter(quotes::Column::Id.eq(quote_id))
+        .filter(quotes::Column::DeletedAt.is_not_null())
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!(
+            "Deleted quote {} not found",
+            quote_id
+        )));
+    }
+    record_revisions(&txn, &[quote_id], RevisionAction::Restore, changed_by).await?;
+
+    let quote = QuoteEntity::find_by_id(quote_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+
+    txn.commit().await?;
+    Ok(quote)
+}
+
+/// Languages whose quote of the day is currently `quote_id`.
+pub async fn fetch_qotd_languages_for_quote<C: ConnectionTrait>(
+    db: &C,
+    quote_id: i32,
+) -> Result<Vec<String>, AppError> {
+    Ok(QOTDEntity::find()
+        .filter(quote_of_the_day::Column::QuoteId.eq(quote_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|qotd| qotd.language)
+        .collect())
+}
+
+pub async fn delete_qotd<C: ConnectionTrait>(db: &C, language: &str) -> Result<(), AppError> {
+    QOTDEntity::delete_many()
+        .filter(quote_of_the_day::Column::Language.eq(language))
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use crate::entities::author_aliases::{self, Entity as AuthorAliasEntity};
+use crate::entities::author_merge_candidates::{self, Entity as AuthorMergeCandidateEntity};
+use crate::entities::author_names::{self, Entity as AuthorNameEntity};
+use crate::entities::authors::{self, Entity as AuthorEntity};
+use crate::entities::quotes::{self, Column as QuoteColumn, Entity as QuoteEntity};
+use crate::models::data::{
+    PageQuery, PagedResponse, ResponseAuthor, ResponseQuote, RevisionAction,
+};
+use crate::models::errors::AppError;
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use std::collections::{BTreeMap, HashMap};
+
+use super::revisions::record_revisions;
+use super::{find_live_quotes, to_response_quotes};
+
+async fn to_response_authors(
+    db: &DatabaseConnection,
+    authors: Vec<authors::Model>,
+) -> Result<Vec<ResponseAuthor>, AppError> {
+    let author_ids: Vec<i32> = authors.iter().map(|author| author.id).collect();
+
+    let mut names: HashMap<i32, BTreeMap<String, String>> = HashMap::new();
+    for name in AuthorNameEntity::find()
+        .filter(author_names::Column::AuthorId.is_in(author_ids.clone()))
+        .all(db)
+        .await?
+    {
+        names
+            .entry(name.author_id)
+            .or_default()
+            .insert(name.language, name.name);
+    }
+
+    let mut aliases: HashMap<i32, Vec<String>> = HashMap::new();
+    for alias in AuthorAliasEntity::find()
+        .filter(author_aliases::Column::AuthorId.is_in(author_ids))
+        .order_by_asc(author_aliases::Column::Alias)
+        .all(db)
+        .await?
+    {
+        aliases
+            .entry(alias.author_id)
+            .or_default()
+            .push(alias.alias);
+    }
+
+    Ok(authors
+        .into_iter()
+        .map(|author| ResponseAuthor {
+            names: names.remove(&author.id).unwrap_or_default(),
+            aliases: aliases.remove(&author.id).unwrap_or_default(),
+            id: author.id,
+            name: author.name,
+            era: author.era,
+            birth_date: author.birth_date,
+            death_date: author.death_date,
+            bio: author.bio,
+        })
+        .collect())
+}
+
+pub async fn fetch_authors(
+    db: &DatabaseConnection,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseAuthor>, AppError> {
+    let paginator = AuthorEntity::find()
+        .order_by_asc(authors::Column::Name)
+        .paginate(db, page.per_page());
+
+    let total = paginator.num_items().await?;
+    let models = paginator.fetch_page(page.page() - 1).await?;
+
+    let items = to_response_authors(db, models).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+pub async fn fetch_author_by_id(
+    db: &DatabaseConnection,
+    author_id: i32,
+) -> Result<ResponseAuthor, AppError> {
+    let author = AuthorEntity::find_by_id(author_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Author {} not found", author_id)))?;
+
+    to_response_authors(db, vec![author])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Author {} not found", author_id)))
+}
+
+pub async fn fetch_quotes_by_author(
+    db: &DatabaseConnection,
+    author_id: i32,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    AuthorEntity::find_by_id(author_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Author {} not found", author_id)))?;
+
+    let paginator = find_live_quotes()
+        .filter(QuoteColumn::AuthorId.eq(author_id))
+        .order_by_asc(QuoteColumn::Id)
+        .paginate(db, page.per_page());
+
+    let total = paginator.num_items().await?;
+    let items = to_response_quotes(db, paginator.fetch_page(page.page() - 1).await?).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+/// Authors sharing a merge key with another author, grouped by key.
+pub async fn fetch_merge_candidates(
+    db: &DatabaseConnection,
+) -> Result<Vec<author_merge_candidates::Model>, AppError> {
+    Ok(AuthorMergeCandidateEntity::find()
+        .order_by_asc(author_merge_candidates::Column::MergeKey)
+        .order_by_asc(author_merge_candidates::Column::AuthorId)
+        .all(db)
+        .await?)
+}
+
+/// Live quote counts of `author_ids`; authors without quotes are left out.
+pub async fn fetch_quote_counts(
+    db: &DatabaseConnection,
+    author_ids: &[i32],
+) -> Result<HashMap<i32, i64>, AppError> {
+    Ok(find_live_quotes()
+        .select_only()
+        .column(QuoteColumn::AuthorId)
+        .column_as(QuoteColumn::Id.count(), "count")
+        .filter(QuoteColumn::AuthorId.is_in(author_ids.iter().copied()))
+        .group_by(QuoteColumn::AuthorId)
+        .into_tuple::<(i32, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+/// Folds author `source_id` into `target_id` in one transaction: its quotes,
+/// aliases and per-language names move over, its name becomes an alias, and
+/// profile fields the target lacks are copied before the source is deleted.
+/// Returns the number of quotes moved.
+pub async fn merge_authors(
+    db: &DatabaseConnection,
+    source_id: i32,
+    target_id: i32,
+    changed_by: Option<&str>,
+) -> Result<u64, AppError> {
+    let txn = db.begin().await?;
+
+    let source = AuthorEntity::find_by_id(source_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Author {} not found", source_id)))?;
+    let target = AuthorEntity::find_by_id(target_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Author {} not found", target_id)))?;
+
+    let quote_ids: Vec<i32> = QuoteEntity::find()
+        .select_only()
+        .column(QuoteColumn::Id)
+        .filter(QuoteColumn::AuthorId.eq(source_id))
+        .into_tuple::<i32>()
+        .all(&txn)
+        .await?;
+    QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            author_id: Set(Some(target_id)),
+            ..Default::default()
+        })
+        .filter(QuoteColumn::AuthorId.eq(source_id))
+        .exec(&txn)
+        .await?;
+
+    AuthorAliasEntity::update_many()
+        .set(author_aliases::ActiveModel {
+            author_id: Set(target_id),
+            ..Default::default()
+        })
+        .filter(author_aliases::Column::AuthorId.eq(source_id))
+        .exec(&txn)
+        .await?;
+    AuthorAliasEntity::insert(author_aliases::ActiveModel {
+        author_id: Set(target_id),
+        alias: Set(source.name.clone()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::column(author_aliases::Column::Alias)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+
+    let names: Vec<author_names::ActiveModel> = AuthorNameEntity::find()
+        .filter(author_names::Column::AuthorId.eq(source_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|name| author_names::ActiveModel {
+            author_id: Set(target_id),
+            language: Set(name.language),
+            name: Set(name.name),
+        })
+        .collect();
+    if !names.is_empty() {
+        AuthorNameEntity::insert_many(names)
+            .on_conflict(
+                OnConflict::columns([
+                    author_names::Column::AuthorId,
+                    author_names::Column::Language,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+
+    let mut merged = target.clone().into_active_model();
+    merged.era = Set(target.era.or(source.era));
+    merged.birth_date = Set(target.birth_date.or(source.birth_date));
+    merged.death_date = Set(target.death_date.or(source.death_date));
+    merged.bio = Set(target.bio.or(source.bio));
+    merged.update(&txn).await?;
+
+    AuthorEntity::delete_by_id(source_id).exec(&txn).await?;
+    record_revisions(&txn, &quote_ids, RevisionAction::Update, changed_by).await?;
+
+    txn.commit().await?;
+    Ok(quote_ids.len() as u64)
+}
@@ -0,0 +1,353 @@
+use crate::entities::categories::{self, Column, Entity as CategoryEntity};
+use crate::entities::category_names::{self, Entity as CategoryNameEntity};
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quotes::{self, Column as QuoteColumn};
+use crate::models::data::{PageQuery, PagedResponse, ResponseQuote, RevisionAction};
+use crate::models::errors::AppError;
+
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, TransactionTrait,
+};
+use std::collections::{BTreeMap, HashMap};
+
+use super::revisions::record_revisions;
+use super::{find_live_quotes, to_response_quotes};
+
+pub async fn fetch_categories(db: &DatabaseConnection) -> Result<Vec<categories::Model>, AppError> {
+    Ok(CategoryEntity::find()
+        .order_by_asc(Column::Name)
+        .all(db)
+        .await?)
+}
+
+pub async fn fetch_category_by_slug(
+    db: &DatabaseConnection,
+    slug: &str,
+) -> Result<categories::Model, AppError> {
+    CategoryEntity::find()
+        .filter(Column::Slug.eq(slug))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Category '{}' not found", slug)))
+}
+
+pub async fn fetch_category_names<C: ConnectionTrait>(
+    db: &C,
+) -> Result<HashMap<i32, BTreeMap<String, String>>, AppError> {
+    let mut names: HashMap<i32, BTreeMap<String, String>> = HashMap::new();
+
+    for name in CategoryNameEntity::find().all(db).await? {
+        names
+            .entry(name.category_id)
+            .or_default()
+            .insert(name.language, name.name);
+    }
+
+    Ok(names)
+}
+
+/// Returns every (category id, quote id, quote language) link.
+pub async fn fetch_category_links(
+    db: &DatabaseConnection,
+) -> Result<Vec<(i32, i32, String)>, AppError> {
+    Ok(QuoteCategoryEntity::find()
+        .select_only()
+        .column(quote_category::Column::CategoryId)
+        .column(quote_category::Column::QuoteId)
+        .column(QuoteColumn::Language)
+        .join(JoinType::InnerJoin, quote_category::Relation::Quotes.def())
+        .filter(QuoteColumn::DeletedAt.is_null())
+        .into_tuple::<(i32, i32, String)>()
+        .all(db)
+        .await?)
+}
+
+pub async fn fetch_quotes_in_categories(
+    db: &DatabaseConnection,
+    category_ids: Vec<i32>,
+    language: Option<&str>,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    let mut query = find_live_quotes().filter(
+        QuoteColumn::Id.in_subquery(
+            Query::select()
+                .column(quote_category::Column::QuoteId)
+                .from(quote_category::Entity)
+                .and_where(quote_category::Column::CategoryId.is_in(category_ids))
+                .to_owned(),
+        ),
+    );
+    if let Some(language) = language {
+        query = query.filter(quotes::Column::Language.eq(language));
+    }
+
+    let paginator = query
+        .order_by_asc(QuoteColumn::Id)
+        .paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = to_response_quotes(db, paginator.fetch_page(page.page() - 1).await?).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+pub async fn fetch_category_by_id(
+    db: &DatabaseConnection,
+    category_id: i32,
+) -> Result<categories::Model, AppError> {
+    CategoryEntity::find_by_id(category_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Category {} not found", category_id)))
+}
+
+/// Returns an existing category other than `except_id` that already uses
+/// `name` or `slug`.
+pub async fn find_conflicting_category(
+    db: &DatabaseConnection,
+    name: Option<&str>,
+    slug: Option<&str>,
+    except_id: Option<i32>,
+) -> Result<Option<categories::Model>, AppError> {
+    let mut condition = Condition::any();
+    if let Some(name) = name {
+        condition = condition.add(Column::Name.eq(name));
+    }
+    if let Some(slug) = slug {
+        condition = condition.add(Column::Slug.eq(slug));
+    }
+
+    let mut query = CategoryEntity::find().filter(condition);
+    if let Some(except_id) = except_id {
+        query = query.filter(Column::Id.ne(except_id));
+    }
+
+    Ok(query.one(db).await?)
+}
+
+async fn upsert_category_names(
+    txn: &DatabaseTransaction,
+    category_id: i32,
+    names: &BTreeMap<String, String>,
+) -> Result<(), AppError> {
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let rows: Vec<category_names::ActiveModel> = names
+        .iter()
+        .map(|(language, name)| category_names::ActiveModel {
+            category_id: Set(category_id),
+            language: Set(language.clone()),
+            name: Set(name.clone()),
+        })
+        .collect();
+
+    CategoryNameEntity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([
+                category_names::Column::CategoryId,
+                category_names::Column::Language,
+            ])
+            .update_column(category_names::Column::Name)
+            .to_owned(),
+        )
+        .exec(txn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn insert_category(
+    db: &DatabaseConnection,
+    category: categories::ActiveModel,
+    names: &BTreeMap<String, String>,
+) -> Result<categories::Model, AppError> {
+    let txn = db.begin().await?;
+
+    let category = CategoryEntity::insert(category)
+        .exec_with_returning(&txn)
+        .await?;
+    upsert_category_names(&txn, category.id, names).await?;
+
+    txn.commit().await?;
+    Ok(category)
+}
+
+pub async fn update_category(
+    db: &DatabaseConnection,
+    category: categories::ActiveModel,
+    names: &BTreeMap<String, String>,
+) -> Result<categories::Model, AppError> {
+    let txn = db.begin().await?;
+
+    let category = category.update(&txn).await?;
+    upsert_category_names(&txn, category.id, names).await?;
+
+    txn.commit().await?;
+    Ok(category)
+}
+
+pub async fn delete_category(
+    db: &DatabaseConnection,
+    category_id: i32,
+    changed_by: Option<&str>,
+) -> Result<(), AppError> {
+    let txn = db.begin().await?;
+
+    let quote_ids = fetch_linked_quote_ids(&txn, category_id).await?;
+    let result = CategoryEntity::delete_by_id(category_id).exec(&txn).await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!(
+            "Category {} not found",
+            category_id
+        )));
+    }
+    record_revisions(&txn, &quote_ids, RevisionAction::Update, changed_by).await?;
+
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn fetch_linked_quote_ids(
+    txn: &DatabaseTransaction,
+    category_id: i32,
+) -> Result<Vec<i32>, AppError> {
+    Ok(QuoteCategoryEntity::find()
+        .filter(quote_category::Column::CategoryId.eq(category_id))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| link.quote_id)
+        .collect())
+}
+
+fn quote_category_conflict() -> OnConflict {
+    OnConflict::columns([
+        quote_category::Column::QuoteId,
+        quote_category::Column::CategoryId,
+    ])
+    .do_nothing()
+    .to_owned()
+}
+
+/// Moves every quote link and child category from `source_id` to `target_id`
+/// and deletes `source_id`, all in one transaction. Returns the number of
+/// quotes newly linked to the target.
+pub async fn merge_categories(
+    db: &DatabaseConnection,
+    source_id: i32,
+    target_id: i32,
+    changed_by: Option<&str>,
+) -> Result<u64, AppError> {
+    let txn = db.begin().await?;
+
+    let quote_ids = fetch_linked_quote_ids(&txn, source_id).await?;
+    let links: Vec<quote_category::ActiveModel> = quote_ids
+        .iter()
+        .map(|quote_id| quote_category::ActiveModel {
+            quote_id: Set(*quote_id),
+            category_id: Set(target_id),
+        })
+        .collect();
+
+    let moved = match links.is_empty() {
+        true => 0,
+        false => {
+            QuoteCategoryEntity::insert_many(links)
+                .on_conflict(quote_category_conflict())
+                .exec_without_returning(&txn)
+                .await?
+        }
+    };
+
+    let source = CategoryEntity::find_by_id(source_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Category {} not found", source_id)))?;
+
+    // A direct child taking over its parent moves up to the parent's place.
+    CategoryEntity::update_many()
+        .set(categories::ActiveModel {
+            parent_id: Set(source.parent_id),
+            ..Default::default()
+        })
+        .filter(Column::Id.eq(target_id))
+        .filter(Column::ParentId.eq(source_id))
+        .exec(&txn)
+        .await?;
+
+    CategoryEntity::update_many()
+        .set(categories::ActiveModel {
+            parent_id: Set(Some(target_id)),
+            ..Default::default()
+        })
+        .filter(Column::ParentId.eq(source_id))
+        .exec(&txn)
+        .await?;
+
+    CategoryEntity::delete_by_id(source_id).exec(&txn).await?;
+    record_revisions(&txn, &quote_ids, RevisionAction::Update, changed_by).await?;
+
+    txn.commit().await?;
+    Ok(moved)
+}
+
+/// Links every quote in `quote_ids` to each of `attach` and unlinks it from
+/// each of `detach`. Returns the number of links added and removed.
+pub async fn bulk_assign_categories(
+    db: &DatabaseConnection,
+    quote_ids: &[i32],
+    attach: &[i32],
+    detach: &[i32],
+    changed_by: Option<&str>,
+) -> Result<(u64, u64), AppError> {
+    let txn = db.begin().await?;
+
+    let links: Vec<quote_category::ActiveModel> = quote_ids
+        .iter()
+        .flat_map(|quote_id| {
+            attach
+                .iter()
+                .map(|category_id| quote_category::ActiveModel {
+                    quote_id: Set(*quote_id),
+                    category_id: Set(*category_id),
+                })
+        })
+        .collect();
+
+    let attached = match links.is_empty() {
+        true => 0,
+        false => {
+            QuoteCategoryEntity::insert_many(links)
+                .on_conflict(quote_category_conflict())
+                .exec_without_returning(&txn)
+                .await?
+        }
+    };
+
+    let detached = match detach.is_empty() {
+        true => 0,
+        false => {
+            QuoteCategoryEntity::delete_many()
+                .filter(quote_category::Column::QuoteId.is_in(quote_ids.iter().copied()))
+                .filter(quote_category::Column::CategoryId.is_in(detach.iter().copied()))
+                .exec(&txn)
+                .await?
+                .rows_affected
+        }
+    };
+
+    record_revisions(&txn, quote_ids, RevisionAction::Update, changed_by).await?;
+
+    txn.commit().await?;
+    Ok((attached, detached))
+}
@@ -0,0 +1,63 @@
+use crate::entities::quote_changes::{self, Entity as QuoteChangeEntity};
+use crate::entities::quotes::{self, Column, Entity as QuoteEntity};
+use crate::models::data::ResponseQuote;
+use crate::models::errors::AppError;
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+use super::{find_live_quotes, to_response_quotes};
+
+/// Change log entries after `since`, in commit order, each with its quote.
+pub async fn fetch_changes(
+    db: &DatabaseConnection,
+    since: i64,
+    language: Option<&str>,
+    limit: u64,
+) -> Result<Vec<(quote_changes::Model, Option<quotes::Model>)>, AppError> {
+    let mut query = QuoteChangeEntity::find().filter(quote_changes::Column::Seq.gt(since));
+    if let Some(language) = language {
+        query = query.filter(quote_changes::Column::Language.eq(language));
+    }
+
+    Ok(query
+        .order_by_asc(quote_changes::Column::Seq)
+        .limit(limit)
+        .find_also_related(QuoteEntity)
+        .all(db)
+        .await?)
+}
+
+/// The newest change cursor for `language`, or across all languages, and 0
+/// when there are no changes. Deletions and moves out of the language count,
+/// so this changes whenever the language's quote set does.
+pub async fn fetch_latest_cursor(
+    db: &DatabaseConnection,
+    language: Option<&str>,
+) -> Result<i64, AppError> {
+    let mut query = QuoteChangeEntity::find()
+        .select_only()
+        .column_as(quote_changes::Column::Seq.max(), "cursor");
+    if let Some(language) = language {
+        query = query.filter(quote_changes::Column::Language.eq(language));
+    }
+
+    Ok(query
+        .into_tuple::<Option<i64>>()
+        .one(db)
+        .await?
+        .flatten()
+        .unwrap_or(0))
+}
+
+pub async fn fetch_language_quotes(
+    db: &DatabaseConnection,
+    language: &str,
+) -> Result<Vec<ResponseQuote>, AppError> {
+    let quotes = find_live_quotes()
+        .filter(Column::Language.eq(language))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?;
+
+    to_response_quotes(db, quotes).await
+}
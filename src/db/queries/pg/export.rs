@@ -0,0 +1,204 @@
+use crate::entities::author_aliases::{self, Entity as AuthorAliasEntity};
+use crate::entities::author_names::{self, Entity as AuthorNameEntity};
+use crate::entities::authors::{self, Entity as AuthorEntity};
+use crate::entities::categories::{self, Entity as CategoryEntity};
+use crate::entities::qotd_history::{self, Entity as QotdHistoryEntity};
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quotes;
+use crate::entities::translation_groups::{self, Entity as TranslationGroupEntity};
+use crate::models::data::{AuthorRecord, CategoryRecord, QuoteRecord, SourceRecord};
+use crate::models::errors::AppError;
+
+use chrono::NaiveDate;
+use sea_orm::{
+    ActiveEnum, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait,
+};
+use std::collections::HashMap;
+
+use super::{categories as category_queries, find_live_quotes, sources};
+
+pub async fn fetch_category_records<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Vec<CategoryRecord>, AppError> {
+    let all = CategoryEntity::find()
+        .order_by_asc(categories::Column::Id)
+        .all(db)
+        .await?;
+    let mut names = category_queries::fetch_category_names(db).await?;
+    let slugs: HashMap<i32, String> = all
+        .iter()
+        .map(|category| (category.id, category.slug.clone()))
+        .collect();
+
+    Ok(all
+        .into_iter()
+        .map(|category| CategoryRecord {
+            parent: category
+                .parent_id
+                .and_then(|parent_id| slugs.get(&parent_id).cloned()),
+            names: names.remove(&category.id).unwrap_or_default(),
+            slug: category.slug,
+            name: category.name,
+        })
+        .collect())
+}
+
+async fn fetch_author_records<C: ConnectionTrait>(
+    db: &C,
+    author_ids: Vec<i32>,
+) -> Result<HashMap<i32, AuthorRecord>, AppError> {
+    let mut records: HashMap<i32, AuthorRecord> = AuthorEntity::find()
+        .filter(authors::Column::Id.is_in(author_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|author| {
+            (
+                author.id,
+                AuthorRecord {
+                    name: author.name,
+                    era: author.era,
+                    birth_date: author.birth_date,
+                    death_date: author.death_date,
+                    bio: author.bio,
+                    names: Default::default(),
+                    aliases: Vec::new(),
+                },
+            )
+        })
+        .collect();
+
+    for name in AuthorNameEntity::find()
+        .filter(author_names::Column::AuthorId.is_in(author_ids.clone()))
+        .all(db)
+        .await?
+    {
+        if let Some(record) = records.get_mut(&name.author_id) {
+            record.names.insert(name.language, name.name);
+        }
+    }
+
+    for alias in AuthorAliasEntity::find()
+        .filter(author_aliases::Column::AuthorId.is_in(author_ids))
+        .order_by_asc(author_aliases::Column::Id)
+        .all(db)
+        .await?
+    {
+        if let Some(record) = records.get_mut(&alias.author_id) {
+            record.aliases.push(alias.alias);
+        }
+    }
+
+    Ok(records)
+}
+
+/// Returns up to `limit` live quotes with ids above `after_id`, in id order,
+/// as export records.
+pub async fn fetch_quote_records<C: ConnectionTrait>(
+    db: &C,
+    after_id: i32,
+    limit: u64,
+) -> Result<Vec<QuoteRecord>, AppError> {
+    let quotes = find_live_quotes()
+        .filter(quotes::Column::Id.gt(after_id))
+        .order_by_asc(quotes::Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    let ids: Vec<i32> = quotes.iter().map(|quote| quote.id).collect();
+    let mut author_ids: Vec<i32> = quotes.iter().filter_map(|quote| quote.author_id).collect();
+    author_ids.sort();
+    author_ids.dedup();
+    let mut group_ids: Vec<i32> = quotes
+        .iter()
+        .filter_map(|quote| quote.translation_group_id)
+        .collect();
+    group_ids.sort();
+    group_ids.dedup();
+
+    let authors = fetch_author_records(db, author_ids).await?;
+    let mut references = sources::fetch_references_for_quotes(db, &ids).await?;
+
+    let originals: HashMap<i32, Option<i32>> = TranslationGroupEntity::find()
+        .filter(translation_groups::Column::Id.is_in(group_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|group| (group.id, group.original_quote_id))
+        .collect();
+
+    let mut categories: HashMap<i32, Vec<String>> = HashMap::new();
+    for (quote_id, slug) in QuoteCategoryEntity::find()
+        .select_only()
+        .column(quote_category::Column::QuoteId)
+        .column(categories::Column::Slug)
+        .join(
+            JoinType::InnerJoin,
+            quote_category::Relation::Categories.def(),
+        )
+        .filter(quote_category::Column::QuoteId.is_in(ids.clone()))
+        .order_by_asc(categories::Column::Slug)
+        .into_tuple::<(i32, String)>()
+        .all(db)
+        .await?
+    {
+        categories.entry(quote_id).or_default().push(slug);
+    }
+
+    let mut qotd_dates: HashMap<i32, Vec<NaiveDate>> = HashMap::new();
+    for entry in QotdHistoryEntity::find()
+        .filter(qotd_history::Column::QuoteId.is_in(ids))
+        .order_by_asc(qotd_history::Column::FeaturedOn)
+        .all(db)
+        .await?
+    {
+        qotd_dates
+            .entry(entry.quote_id)
+            .or_default()
+            .push(entry.featured_on);
+    }
+
+    Ok(quotes
+        .into_iter()
+        .map(|quote| QuoteRecord {
+            id: Some(quote.id),
+            author_record: quote
+                .author_id
+                .and_then(|author_id| authors.get(&author_id).cloned()),
+            references: references
+                .remove(&quote.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|reference| SourceRecord {
+                    kind: reference.kind,
+                    title: reference.title,
+                    volume: reference.volume,
+                    chapter: reference.chapter,
+                    page: reference.page,
+                    collection: reference.collection,
+                    number: reference.number,
+                    edition: reference.edition,
+                    publisher: reference.publisher,
+                    year: reference.year,
+                    url: reference.url,
+                })
+                .collect(),
+            categories: categories.remove(&quote.id).unwrap_or_default(),
+            translation_original: quote.translation_group_id.is_some_and(|group_id| {
+                originals.get(&group_id).copied().flatten() == Some(quote.id)
+            }),
+            qotd_dates: qotd_dates.remove(&quote.id).unwrap_or_default(),
+            content: quote.quote,
+            author: quote.author,
+            author_id: quote.author_id,
+            reference: quote.reference.unwrap_or_default(),
+            language: quote.language,
+            attribution_status: Some(quote.attribution_status.to_value()),
+            grading_notes: quote.grading_notes,
+            graded_by: quote.graded_by,
+            translation_group_id: quote.translation_group_id,
+        })
+        .collect())
+}
@@ -0,0 +1,94 @@
+use crate::entities::favorites::{self, Column, Entity as FavoriteEntity};
+use crate::entities::quotes::{self, Column as QuoteColumn};
+use crate::models::data::{PageQuery, PagedResponse, ResponseQuote};
+use crate::models::errors::AppError;
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait,
+};
+
+use super::{find_live_quotes, to_response_quotes};
+
+/// Saves `quote_id` for the owner. Returns false when it was already saved.
+pub async fn add_favorite(
+    db: &DatabaseConnection,
+    key_id: &str,
+    end_user_id: &str,
+    quote_id: i32,
+) -> Result<bool, AppError> {
+    let inserted = FavoriteEntity::insert(favorites::ActiveModel {
+        key_id: Set(key_id.to_string()),
+        end_user_id: Set(end_user_id.to_string()),
+        quote_id: Set(quote_id),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([Column::KeyId, Column::EndUserId, Column::QuoteId])
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(inserted > 0)
+}
+
+pub async fn remove_favorite(
+    db: &DatabaseConnection,
+    key_id: &str,
+    end_user_id: &str,
+    quote_id: i32,
+) -> Result<(), AppError> {
+    FavoriteEntity::delete_many()
+        .filter(Column::KeyId.eq(key_id))
+        .filter(Column::EndUserId.eq(end_user_id))
+        .filter(Column::QuoteId.eq(quote_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// The owner's live favourites, most recently saved first.
+pub async fn fetch_favorites(
+    db: &DatabaseConnection,
+    key_id: &str,
+    end_user_id: &str,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    let paginator = find_live_quotes()
+        .join(JoinType::InnerJoin, quotes::Relation::Favorites.def())
+        .filter(Column::KeyId.eq(key_id))
+        .filter(Column::EndUserId.eq(end_user_id))
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(QuoteColumn::Id)
+        .paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = to_response_quotes(db, paginator.fetch_page(page.page() - 1).await?).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+/// Ids of the owner's live favourites.
+pub async fn fetch_favorite_ids(
+    db: &DatabaseConnection,
+    key_id: &str,
+    end_user_id: &str,
+) -> Result<Vec<i32>, AppError> {
+    Ok(find_live_quotes()
+        .select_only()
+        .column(QuoteColumn::Id)
+        .join(JoinType::InnerJoin, quotes::Relation::Favorites.def())
+        .filter(Column::KeyId.eq(key_id))
+        .filter(Column::EndUserId.eq(end_user_id))
+        .into_tuple::<i32>()
+        .all(db)
+        .await?)
+}
@@ -0,0 +1,470 @@
+use crate::entities::author_aliases::{self, Entity as AuthorAliasEntity};
+use crate::entities::author_names::{self, Entity as AuthorNameEntity};
+use crate::entities::authors::{self, Entity as AuthorEntity};
+use crate::entities::categories::{self, Entity as CategoryEntity};
+use crate::entities::category_names::{self, Entity as CategoryNameEntity};
+use crate::entities::qotd_history::{self, Entity as QotdHistoryEntity};
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quote_source::{self, Entity as QuoteSourceEntity};
+use crate::entities::quotes::{self, Entity as QuoteEntity};
+use crate::entities::sources::{self, Entity as SourceEntity};
+use crate::entities::translation_groups::{self, Entity as TranslationGroupEntity};
+use crate::models::data::{AuthorRecord, CategoryRecord, RevisionAction, SourceRecord};
+use crate::models::errors::AppError;
+
+use chrono::NaiveDate;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+
+use super::find_live_quotes;
+use super::revisions::record_revisions;
+
+/// A validated import row, with its categories resolved to slugs.
+pub struct PlannedQuote {
+    pub quote: quotes::ActiveModel,
+    pub author: Option<AuthorRecord>,
+    pub references: Vec<SourceRecord>,
+    pub category_slugs: Vec<String>,
+    pub translation_group: Option<i32>,
+    pub translation_original: bool,
+    pub qotd_dates: Vec<NaiveDate>,
+}
+
+pub async fn fetch_existing_author_ids(
+    db: &DatabaseConnection,
+    author_ids: &[i32],
+) -> Result<HashSet<i32>, AppError> {
+    if author_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    Ok(AuthorEntity::find()
+        .select_only()
+        .column(authors::Column::Id)
+        .filter(authors::Column::Id.is_in(author_ids.iter().copied()))
+        .into_tuple::<i32>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+/// Returns (id, language, content) for every live quote, oldest first,
+/// limited to `languages` when given.
+pub async fn fetch_contents(
+    db: &DatabaseConnection,
+    languages: Option<&[String]>,
+) -> Result<Vec<(i32, String, String)>, AppError> {
+    let mut query = find_live_quotes()
+        .select_only()
+        .column(quotes::Column::Id)
+        .column(quotes::Column::Language)
+        .column(quotes::Column::Quote)
+        .order_by_asc(quotes::Column::Id);
+    if let Some(languages) = languages {
+        query = query.filter(quotes::Column::Language.is_in(languages.iter().cloned()));
+    }
+
+    Ok(query.into_tuple::<(i32, String, String)>().all(db).await?)
+}
+
+/// Finds the author an import record refers to: one named `author.name`,
+/// else the owner of that name as an alias, else an author named or aliased
+/// like one of the record's aliases.
+pub async fn find_author<C: ConnectionTrait>(
+    conn: &C,
+    author: &AuthorRecord,
+) -> Result<Option<i32>, AppError> {
+    let name = author.name.trim();
+    if let Some(existing) = AuthorEntity::find()
+        .filter(authors::Column::Name.eq(name))
+        .one(conn)
+        .await?
+    {
+        return Ok(Some(existing.id));
+    }
+
+    let owners = fetch_alias_owners(conn, std::slice::from_ref(&author.name)).await?;
+    if let Some(author_id) = owners.get(name) {
+        return Ok(Some(*author_id));
+    }
+
+    if author.aliases.is_empty() {
+        return Ok(None);
+    }
+    if let Some(existing) = AuthorEntity::find()
+        .filter(authors::Column::Name.is_in(author.aliases.iter().map(|alias| alias.trim())))
+        .order_by_asc(authors::Column::Id)
+        .one(conn)
+        .await?
+    {
+        return Ok(Some(existing.id));
+    }
+
+    Ok(fetch_alias_owners(conn, &author.aliases)
+        .await?
+        .into_values()
+        .min())
+}
+
+/// Maps each of `aliases` that is already taken to the author owning it.
+pub async fn fetch_alias_owners<C: ConnectionTrait>(
+    conn: &C,
+    aliases: &[String],
+) -> Result<HashMap<String, i32>, AppError> {
+    let aliases: Vec<&str> = aliases.iter().map(|alias| alias.trim()).collect();
+    if aliases.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(AuthorAliasEntity::find()
+        .filter(author_aliases::Column::Alias.is_in(aliases))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|alias| (alias.alias, alias.author_id))
+        .collect())
+}
+
+/// Returns (translation_group_id, language, content) for the live members of
+/// `group_ids`.
+pub async fn fetch_group_members(
+    db: &DatabaseConnection,
+    group_ids: &[i32],
+) -> Result<Vec<(i32, String, String)>, AppError> {
+    if group_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(find_live_quotes()
+        .select_only()
+        .column(quotes::Column::TranslationGroupId)
+        .column(quotes::Column::Language)
+        .column(quotes::Column::Quote)
+        .filter(quotes::Column::TranslationGroupId.is_in(group_ids.iter().copied()))
+        .into_tuple::<(i32, String, String)>()
+        .all(db)
+        .await?)
+}
+
+async fn insert_categories(
+    txn: &DatabaseTransaction,
+    new_categories: &[CategoryRecord],
+    category_ids: &mut HashMap<String, i32>,
+) -> Result<(), AppError> {
+    for category in new_categories {
+        let inserted = CategoryEntity::insert(categories::ActiveModel {
+            name: Set(category.name.clone()),
+            slug: Set(category.slug.clone()),
+            ..Default::default()
+        })
+        .exec_with_returning(txn)
+        .await?;
+        category_ids.insert(inserted.slug, inserted.id);
+    }
+
+    for category in new_categories {
+        if let Some(parent_id) = category
+            .parent
+            .as_ref()
+            .and_then(|parent| category_ids.get(parent))
+        {
+            CategoryEntity::update_many()
+                .set(categories::ActiveModel {
+                    parent_id: Set(Some(*parent_id)),
+                    ..Default::default()
+                })
+                .filter(categories::Column::Slug.eq(category.slug.as_str()))
+                .exec(txn)
+                .await?;
+        }
+
+        let names: Vec<category_names::ActiveModel> = category
+            .names
+            .iter()
+            .filter_map(|(language, name)| {
+                category_ids
+                    .get(&category.slug)
+                    .map(|category_id| category_names::ActiveModel {
+                        category_id: Set(*category_id),
+                        language: Set(language.clone()),
+                        name: Set(name.clone()),
+                    })
+            })
+            .collect();
+
+        if !names.is_empty() {
+            CategoryNameEntity::insert_many(names).exec(txn).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the id of the author `author` refers to, creating the author with
+/// its localised names and aliases when there is none yet. Aliases already
+/// owned by another author are skipped.
+async fn resolve_author(txn: &DatabaseTransaction, author: &AuthorRecord) -> Result<i32, AppError> {
+    if let Some(author_id) = find_author(txn, author).await? {
+        return Ok(author_id);
+    }
+
+    let name = author.name.trim();
+    let inserted = AuthorEntity::insert(authors::ActiveModel {
+        name: Set(name.to_string()),
+        era: Set(author.era.clone()),
+        birth_date: Set(author.birth_date),
+        death_date: Set(author.death_date),
+        bio: Set(author.bio.clone()),
+        ..Default::default()
+    })
+    .exec_with_returning(txn)
+    .await?;
+
+    let names: Vec<author_names::ActiveModel> = author
+        .names
+        .iter()
+        .map(|(language, name)| author_names::ActiveModel {
+            author_id: Set(inserted.id),
+            language: Set(language.clone()),
+            name: Set(name.clone()),
+        })
+        .collect();
+    if !names.is_empty() {
+        AuthorNameEntity::insert_many(names).exec(txn).await?;
+    }
+
+    let aliases: Vec<author_aliases::ActiveModel> = author
+        .aliases
+        .iter()
+        .map(|alias| author_aliases::ActiveModel {
+            author_id: Set(inserted.id),
+            alias: Set(alias.trim().to_string()),
+            ..Default::default()
+        })
+        .collect();
+    if !aliases.is_empty() {
+        AuthorAliasEntity::insert_many(aliases)
+            .on_conflict(
+                OnConflict::column(author_aliases::Column::Alias)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(txn)
+            .await?;
+    }
+
+    Ok(inserted.id)
+}
+
+fn optional_eq<C: ColumnTrait, V: Into<sea_orm::Value>>(column: C, value: Option<V>) -> Condition {
+    match value {
+        Some(value) => Condition::all().add(column.eq(value)),
+        None => Condition::all().add(column.is_null()),
+    }
+}
+
+/// Returns the id of a source identical to `source`, creating it if needed.
+async fn resolve_source(txn: &DatabaseTransaction, source: &SourceRecord) -> Result<i32, AppError> {
+    let existing = SourceEntity::find()
+        .filter(sources::Column::Kind.eq(source.kind.as_str()))
+        .filter(sources::Column::Title.eq(source.title.as_str()))
+        .filter(optional_eq(sources::Column::Volume, source.volume.clone()))
+        .filter(optional_eq(
+            sources::Column::Chapter,
+            source.chapter.clone(),
+        ))
+        .filter(optional_eq(sources::Column::Page, source.page.clone()))
+        .filter(optional_eq(
+            sources::Column::Collection,
+            source.collection.clone(),
+        ))
+        .filter(optional_eq(sources::Column::Number, source.number.clone()))
+        .filter(optional_eq(
+            sources::Column::Edition,
+            source.edition.clone(),
+        ))
+        .filter(optional_eq(
+            sources::Column::Publisher,
+            source.publisher.clone(),
+        ))
+        .filter(optional_eq(sources::Column::Year, source.year))
+        .filter(optional_eq(sources::Column::Url, source.url.clone()))
+        .one(txn)
+        .await?;
+
+    if let Some(existing) = existing {
+        return Ok(existing.id);
+    }
+
+    let inserted = SourceEntity::insert(sources::ActiveModel {
+        kind: Set(source.kind.clone()),
+        title: Set(source.title.clone()),
+        volume: Set(source.volume.clone()),
+        chapter: Set(source.chapter.clone()),
+        page: Set(source.page.clone()),
+        collection: Set(source.collection.clone()),
+        number: Set(source.number.clone()),
+        edition: Set(source.edition.clone()),
+        publisher: Set(source.publisher.clone()),
+        year: Set(source.year),
+        url: Set(source.url.clone()),
+        ..Default::default()
+    })
+    .exec_with_returning(txn)
+    .await?;
+
+    Ok(inserted.id)
+}
+
+/// Creates `new_categories`, then inserts every quote with its author,
+/// sources, category links, translation group and QOTD history, all in one
+/// transaction. `category_ids` maps the slugs of existing categories to their
+/// ids. Translation group ids in `existing_groups` are kept; every other
+/// group id in the file gets a new group.
+pub async fn apply_import(
+    db: &DatabaseConnection,
+    new_categories: &[CategoryRecord],
+    mut category_ids: HashMap<String, i32>,
+    existing_groups: &HashSet<i32>,
+    quotes: Vec<PlannedQuote>,
+    changed_by: Option<&str>,
+) -> Result<Vec<i32>, AppError> {
+    let txn = db.begin().await?;
+
+    insert_categories(&txn, new_categories, &mut category_ids).await?;
+
+    let mut author_ids: HashMap<String, i32> = HashMap::new();
+    let mut source_ids: HashMap<SourceRecord, i32> = HashMap::new();
+    let mut group_ids: HashMap<i32, i32> = existing_groups
+        .iter()
+        .map(|group_id| (*group_id, *group_id))
+        .collect();
+    let mut quote_ids = Vec::with_capacity(quotes.len());
+
+    for planned in quotes {
+        let mut quote = planned.quote;
+
+        if let Some(author) = &planned.author {
+            let author_id = match author_ids.get(author.name.trim()) {
+                Some(author_id) => *author_id,
+                None => {
+                    let author_id = resolve_author(&txn, author).await?;
+                    author_ids.insert(author.name.trim().to_string(), author_id);
+                    author_id
+                }
+            };
+            quote.author_id = Set(Some(author_id));
+        }
+
+        if let Some(key) = planned.translation_group {
+            let group_id = match group_ids.get(&key) {
+                Some(group_id) => *group_id,
+                None => {
+                    let group = TranslationGroupEntity::insert(translation_groups::ActiveModel {
+                        ..Default::default()
+                    })
+                    .exec_with_returning(&txn)
+                    .await?;
+                    group_ids.insert(key, group.id);
+                    group.id
+                }
+            };
+            quote.translation_group_id = Set(Some(group_id));
+        }
+
+        let quote = QuoteEntity::insert(quote).exec_with_returning(&txn).await?;
+
+        if planned.translation_original {
+            if let Some(group_id) = quote.translation_group_id {
+                TranslationGroupEntity::update_many()
+                    .set(translation_groups::ActiveModel {
+                        original_quote_id: Set(Some(quote.id)),
+                        ..Default::default()
+                    })
+                    .filter(translation_groups::Column::Id.eq(group_id))
+                    .exec(&txn)
+                    .await?;
+            }
+        }
+
+        let mut links = Vec::new();
+        for (position, source) in planned.references.iter().enumerate() {
+            let source_id = match source_ids.get(source) {
+                Some(source_id) => *source_id,
+                None => {
+                    let source_id = resolve_source(&txn, source).await?;
+                    source_ids.insert(source.clone(), source_id);
+                    source_id
+                }
+            };
+            if links
+                .iter()
+                .all(|link: &quote_source::ActiveModel| link.source_id.as_ref() != &source_id)
+            {
+                links.push(quote_source::ActiveModel {
+                    quote_id: Set(quote.id),
+                    source_id: Set(source_id),
+                    position: Set(position as i32),
+                });
+            }
+        }
+        if !links.is_empty() {
+            QuoteSourceEntity::insert_many(links).exec(&txn).await?;
+        }
+
+        let links: Vec<quote_category::ActiveModel> = planned
+            .category_slugs
+            .iter()
+            .filter_map(|slug| category_ids.get(slug))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|category_id| quote_category::ActiveModel {
+                quote_id: Set(quote.id),
+                category_id: Set(*category_id),
+            })
+            .collect();
+        if !links.is_empty() {
+            QuoteCategoryEntity::insert_many(links).exec(&txn).await?;
+        }
+
+        let history: Vec<qotd_history::ActiveModel> = planned
+            .qotd_dates
+            .iter()
+            .map(|date| qotd_history::ActiveModel {
+                language: Set(quote.language.clone()),
+                quote_id: Set(quote.id),
+                featured_on: Set(*date),
+                ..Default::default()
+            })
+            .collect();
+        if !history.is_empty() {
+            QotdHistoryEntity::insert_many(history)
+                .on_conflict(
+                    OnConflict::columns([
+                        qotd_history::Column::Language,
+                        qotd_history::Column::FeaturedOn,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(&txn)
+                .await?;
+        }
+
+        quote_ids.push(quote.id);
+    }
+
+    // Chunked to stay below Postgres' bind parameter limit.
+    for chunk in quote_ids.chunks(1000) {
+        record_revisions(&txn, chunk, RevisionAction::Create, changed_by).await?;
+    }
+
+    txn.commit().await?;
+    Ok(quote_ids)
+}
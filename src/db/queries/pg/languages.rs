@@ -0,0 +1,83 @@
+use crate::entities::languages::{self, Column, Entity as LanguageEntity};
+use crate::models::errors::AppError;
+
+use crate::entities::quotes::Column as QuoteColumn;
+
+use super::find_live_quotes;
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::collections::HashMap;
+
+pub async fn fetch_languages(
+    db: &DatabaseConnection,
+    enabled_only: bool,
+) -> Result<Vec<languages::Model>, AppError> {
+    let mut query = LanguageEntity::find().order_by_asc(Column::Code);
+
+    if enabled_only {
+        query = query.filter(Column::Enabled.eq(true));
+    }
+
+    Ok(query.all(db).await?)
+}
+
+pub async fn fetch_quote_counts(db: &DatabaseConnection) -> Result<HashMap<String, i64>, AppError> {
+    Ok(find_live_quotes()
+        .select_only()
+        .column(QuoteColumn::Language)
+        .column_as(QuoteColumn::Id.count(), "count")
+        .group_by(QuoteColumn::Language)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+pub async fn fetch_qotd_languages(
+    db: &DatabaseConnection,
+) -> Result<Vec<languages::Model>, AppError> {
+    Ok(LanguageEntity::find()
+        .filter(Column::Enabled.eq(true))
+        .filter(Column::QotdEnabled.eq(true))
+        .order_by_asc(Column::Code)
+        .all(db)
+        .await?)
+}
+
+pub async fn fetch_language(
+    db: &DatabaseConnection,
+    code: &str,
+) -> Result<Option<languages::Model>, AppError> {
+    Ok(LanguageEntity::find_by_id(code.to_string()).one(db).await?)
+}
+
+pub async fn fetch_directions(
+    db: &DatabaseConnection,
+    codes: &[String],
+) -> Result<HashMap<String, String>, AppError> {
+    if codes.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(LanguageEntity::find()
+        .filter(Column::Code.is_in(codes.to_vec()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|lang| (lang.code, lang.direction))
+        .collect())
+}
+
+/// The ISO 15924 script of every registered language, by code.
+pub async fn fetch_scripts(db: &DatabaseConnection) -> Result<HashMap<String, String>, AppError> {
+    Ok(LanguageEntity::find()
+        .select_only()
+        .column(Column::Code)
+        .column(Column::Script)
+        .into_tuple::<(String, String)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect())
+}
@@ -0,0 +1,43 @@
+use crate::entities::quote_source::{self, Entity as QuoteSourceEntity};
+use crate::entities::quotes::{self, Column};
+use crate::models::errors::AppError;
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::collections::HashSet;
+
+use super::find_live_quotes;
+
+/// Up to `limit` live quotes with ids above `after_id`, in id order.
+pub async fn fetch_quote_batch(
+    db: &DatabaseConnection,
+    after_id: i32,
+    limit: u64,
+) -> Result<Vec<quotes::Model>, AppError> {
+    Ok(find_live_quotes()
+        .filter(Column::Id.gt(after_id))
+        .order_by_asc(Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// The ids in `quote_ids` that are linked to at least one source.
+pub async fn fetch_ids_with_sources(
+    db: &DatabaseConnection,
+    quote_ids: &[i32],
+) -> Result<HashSet<i32>, AppError> {
+    if quote_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    Ok(QuoteSourceEntity::find()
+        .select_only()
+        .column(quote_source::Column::QuoteId)
+        .distinct()
+        .filter(quote_source::Column::QuoteId.is_in(quote_ids.iter().copied()))
+        .into_tuple::<i32>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect())
+}
@@ -0,0 +1,137 @@
+use crate::entities::quote_ratings::{self, Column, Entity as RatingEntity};
+use crate::entities::quotes::{self, Column as QuoteColumn};
+use crate::models::data::PageQuery;
+use crate::models::errors::AppError;
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, JoinType, Order, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Select,
+};
+use std::collections::HashMap;
+
+/// (quote id, number of ratings, sum of their values)
+pub type RatingStats = (i32, i64, i64);
+
+pub async fn upsert_rating(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+    value: i16,
+) -> Result<(), AppError> {
+    RatingEntity::insert(quote_ratings::ActiveModel {
+        key_id: Set(key_id.to_string()),
+        quote_id: Set(quote_id),
+        value: Set(value),
+        updated_at: Set(Utc::now().into()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([Column::KeyId, Column::QuoteId])
+            .update_columns([Column::Value, Column::UpdatedAt])
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_rating(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+) -> Result<(), AppError> {
+    RatingEntity::delete_many()
+        .filter(Column::KeyId.eq(key_id))
+        .filter(Column::QuoteId.eq(quote_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn fetch_rating(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+) -> Result<Option<i16>, AppError> {
+    Ok(RatingEntity::find_by_id((key_id.to_string(), quote_id))
+        .one(db)
+        .await?
+        .map(|rating| rating.value))
+}
+
+/// Ratings of live quotes, grouped per quote.
+fn live_stats() -> Select<RatingEntity> {
+    RatingEntity::find()
+        .select_only()
+        .column(Column::QuoteId)
+        .column_as(Column::Value.count(), "ratings")
+        .column_as(Expr::cust("SUM(quote_ratings.value)::BIGINT"), "total")
+        .join(JoinType::InnerJoin, quote_ratings::Relation::Quotes.def())
+        .filter(QuoteColumn::DeletedAt.is_null())
+        .group_by(Column::QuoteId)
+}
+
+pub async fn fetch_quote_stats(
+    db: &DatabaseConnection,
+    quote_id: i32,
+) -> Result<Option<RatingStats>, AppError> {
+    Ok(live_stats()
+        .filter(Column::QuoteId.eq(quote_id))
+        .into_tuple::<RatingStats>()
+        .one(db)
+        .await?)
+}
+
+/// Rating counts and sums for every rated live quote in `language`.
+pub async fn fetch_language_stats(
+    db: &DatabaseConnection,
+    language: &str,
+) -> Result<HashMap<i32, (i64, i64)>, AppError> {
+    Ok(live_stats()
+        .filter(quotes::Column::Language.eq(language))
+        .into_tuple::<RatingStats>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(quote_id, ratings, total)| (quote_id, (ratings, total)))
+        .collect())
+}
+
+/// Rated live quotes ordered by their Bayesian average, which pulls quotes
+/// with few ratings towards `prior_mean` as if they had `prior_weight` extra
+/// ratings of it. Only ratings given or changed since `since` count.
+pub async fn fetch_popular(
+    db: &DatabaseConnection,
+    language: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    prior_mean: f64,
+    prior_weight: f64,
+    page: &PageQuery,
+) -> Result<(Vec<RatingStats>, u64), AppError> {
+    let mut query = live_stats();
+    if let Some(language) = language {
+        query = query.filter(quotes::Column::Language.eq(language));
+    }
+    if let Some(since) = since {
+        query = query.filter(Column::UpdatedAt.gte(since));
+    }
+
+    let paginator = query
+        .order_by(
+            Expr::cust_with_values(
+                "(? * ? + SUM(quote_ratings.value)) / (? + COUNT(quote_ratings.value))",
+                [prior_weight, prior_mean, prior_weight],
+            ),
+            Order::Desc,
+        )
+        .order_by_asc(Column::QuoteId)
+        .into_tuple::<RatingStats>()
+        .paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = paginator.fetch_page(page.page() - 1).await?;
+
+    Ok((items, total))
+}
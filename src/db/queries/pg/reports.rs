@@ -0,0 +1,123 @@
+use crate::entities::quote_reports::{self, Column, Entity as ReportEntity};
+use crate::models::data::{PageQuery, PagedResponse, ReportQuery, ReportStatus};
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
+};
+
+pub async fn insert_report(
+    db: &DatabaseConnection,
+    report: quote_reports::ActiveModel,
+) -> Result<quote_reports::Model, AppError> {
+    Ok(ReportEntity::insert(report).exec_with_returning(db).await?)
+}
+
+pub async fn fetch_report<C: ConnectionTrait>(
+    db: &C,
+    report_id: i32,
+) -> Result<quote_reports::Model, AppError> {
+    ReportEntity::find_by_id(report_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report {} not found", report_id)))
+}
+
+/// Whether `reported_by` already has an open report on `quote_id`.
+pub async fn has_open_report(
+    db: &DatabaseConnection,
+    quote_id: i32,
+    reported_by: &str,
+) -> Result<bool, AppError> {
+    Ok(ReportEntity::find()
+        .filter(Column::QuoteId.eq(quote_id))
+        .filter(Column::ReportedBy.eq(reported_by))
+        .filter(Column::Status.eq(ReportStatus::Open.as_str()))
+        .count(db)
+        .await?
+        > 0)
+}
+
+/// The triage queue, oldest first.
+pub async fn fetch_reports(
+    db: &DatabaseConnection,
+    query: &ReportQuery,
+    page: &PageQuery,
+) -> Result<PagedResponse<quote_reports::Model>, AppError> {
+    let mut select = ReportEntity::find();
+    if let Some(status) = query.status {
+        select = select.filter(Column::Status.eq(status.as_str()));
+    }
+    if let Some(reason) = query.reason {
+        select = select.filter(Column::Reason.eq(reason.as_str()));
+    }
+    if let Some(quote_id) = query.quote_id {
+        select = select.filter(Column::QuoteId.eq(quote_id));
+    }
+
+    let paginator = select
+        .order_by_asc(Column::Id)
+        .paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = paginator.fetch_page(page.page() - 1).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+/// Closes an open report as resolved or dismissed. Fails when another admin
+/// closed it first.
+pub async fn close_report<C: ConnectionTrait>(
+    db: &C,
+    report_id: i32,
+    status: ReportStatus,
+    resolution: Option<String>,
+    revision: Option<i32>,
+    resolved_by: Option<&str>,
+) -> Result<quote_reports::Model, AppError> {
+    let result = ReportEntity::update_many()
+        .set(quote_reports::ActiveModel {
+            status: Set(status.as_str().to_string()),
+            resolution: Set(resolution),
+            revision: Set(revision),
+            resolved_by: Set(resolved_by.map(str::to_string)),
+            resolved_at: Set(Some(Utc::now().into())),
+            ..Default::default()
+        })
+        .filter(Column::Id.eq(report_id))
+        .filter(Column::Status.eq(ReportStatus::Open.as_str()))
+        .exec(db)
+        .await?;
+
+    match result.rows_affected {
+        0 => Err(AppError::BadRequest(format!(
+            "Report {} is no longer open",
+            report_id
+        ))),
+        _ => fetch_report(db, report_id).await,
+    }
+}
+
+/// Keeps the revision a resolving edit produced on its report.
+pub async fn set_report_revision<C: ConnectionTrait>(
+    db: &C,
+    report_id: i32,
+    revision: Option<i32>,
+) -> Result<quote_reports::Model, AppError> {
+    ReportEntity::update_many()
+        .set(quote_reports::ActiveModel {
+            revision: Set(revision),
+            ..Default::default()
+        })
+        .filter(Column::Id.eq(report_id))
+        .exec(db)
+        .await?;
+    fetch_report(db, report_id).await
+}
@@ -0,0 +1,139 @@
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quote_revisions::{self, Column, Entity as QuoteRevisionEntity};
+use crate::entities::quotes::{self, Entity as QuoteEntity};
+use crate::models::data::RevisionAction;
+use crate::models::errors::AppError;
+
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use std::collections::HashMap;
+
+/// Stores the current text, author, reference and categories of each quote in
+/// `quote_ids` as its next revision. Updates that leave all of them unchanged
+/// are not recorded.
+pub async fn record_revisions(
+    txn: &DatabaseTransaction,
+    quote_ids: &[i32],
+    action: RevisionAction,
+    changed_by: Option<&str>,
+) -> Result<(), AppError> {
+    if quote_ids.is_empty() {
+        return Ok(());
+    }
+
+    let quotes = QuoteEntity::find()
+        .filter(quotes::Column::Id.is_in(quote_ids.iter().copied()))
+        .all(txn)
+        .await?;
+
+    let mut categories: HashMap<i32, Vec<i32>> = HashMap::new();
+    for link in QuoteCategoryEntity::find()
+        .filter(quote_category::Column::QuoteId.is_in(quote_ids.iter().copied()))
+        .order_by_asc(quote_category::Column::CategoryId)
+        .all(txn)
+        .await?
+    {
+        categories
+            .entry(link.quote_id)
+            .or_default()
+            .push(link.category_id);
+    }
+
+    let latest: HashMap<i32, quote_revisions::Model> = QuoteRevisionEntity::find()
+        .distinct_on([Column::QuoteId])
+        .filter(Column::QuoteId.is_in(quote_ids.iter().copied()))
+        .order_by_asc(Column::QuoteId)
+        .order_by_desc(Column::Revision)
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|revision| (revision.quote_id, revision))
+        .collect();
+
+    let revisions: Vec<quote_revisions::ActiveModel> = quotes
+        .into_iter()
+        .filter_map(|quote| {
+            let category_ids = categories.remove(&quote.id).unwrap_or_default();
+            let previous = latest.get(&quote.id);
+            let unchanged = previous.is_some_and(|previous| {
+                previous.content == quote.quote
+                    && previous.author == quote.author
+                    && previous.author_id == quote.author_id
+                    && previous.reference == quote.reference
+                    && previous.category_ids == category_ids
+            });
+            if action == RevisionAction::Update && unchanged {
+                return None;
+            }
+
+            Some(quote_revisions::ActiveModel {
+                quote_id: Set(quote.id),
+                revision: Set(previous.map_or(1, |previous| previous.revision + 1)),
+                action: Set(action.as_str().to_string()),
+                content: Set(quote.quote),
+                author: Set(quote.author),
+                author_id: Set(quote.author_id),
+                reference: Set(quote.reference),
+                category_ids: Set(category_ids),
+                changed_by: Set(changed_by.map(str::to_string)),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    if !revisions.is_empty() {
+        QuoteRevisionEntity::insert_many(revisions)
+            .exec(txn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Revisions of `quote_id`, newest first.
+pub async fn fetch_revisions(
+    db: &DatabaseConnection,
+    quote_id: i32,
+) -> Result<Vec<quote_revisions::Model>, AppError> {
+    Ok(QuoteRevisionEntity::find()
+        .filter(Column::QuoteId.eq(quote_id))
+        .order_by_desc(Column::Revision)
+        .all(db)
+        .await?)
+}
+
+/// The newest revision number of `quote_id`, if it has any.
+pub async fn fetch_latest_revision<C: ConnectionTrait>(
+    db: &C,
+    quote_id: i32,
+) -> Result<Option<i32>, AppError> {
+    Ok(QuoteRevisionEntity::find()
+        .select_only()
+        .column(Column::Revision)
+        .filter(Column::QuoteId.eq(quote_id))
+        .order_by_desc(Column::Revision)
+        .into_tuple::<i32>()
+        .one(db)
+        .await?)
+}
+
+pub async fn fetch_revision(
+    db: &DatabaseConnection,
+    quote_id: i32,
+    revision: i32,
+) -> Result<quote_revisions::Model, AppError> {
+    QuoteRevisionEntity::find()
+        .filter(Column::QuoteId.eq(quote_id))
+        .filter(Column::Revision.eq(revision))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Revision {} of quote {} not found",
+                revision, quote_id
+            ))
+        })
+}
@@ -0,0 +1,54 @@
+use crate::entities::quote_source::{self, Entity as QuoteSourceEntity};
+use crate::entities::sources::{self, Entity as SourceEntity};
+use crate::models::data::ResponseReference;
+use crate::models::errors::AppError;
+
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder};
+use std::collections::HashMap;
+
+pub fn to_response_reference(source: sources::Model) -> ResponseReference {
+    ResponseReference {
+        id: source.id,
+        kind: source.kind,
+        title: source.title,
+        volume: source.volume,
+        chapter: source.chapter,
+        page: source.page,
+        collection: source.collection,
+        number: source.number,
+        edition: source.edition,
+        publisher: source.publisher,
+        year: source.year,
+        url: source.url,
+    }
+}
+
+pub async fn fetch_references_for_quotes<C: ConnectionTrait>(
+    db: &C,
+    quote_ids: &[i32],
+) -> Result<HashMap<i32, Vec<ResponseReference>>, AppError> {
+    let mut references: HashMap<i32, Vec<ResponseReference>> = HashMap::new();
+
+    if quote_ids.is_empty() {
+        return Ok(references);
+    }
+
+    let links = QuoteSourceEntity::find()
+        .filter(quote_source::Column::QuoteId.is_in(quote_ids.to_vec()))
+        .order_by_asc(quote_source::Column::QuoteId)
+        .order_by_asc(quote_source::Column::Position)
+        .find_also_related(SourceEntity)
+        .all(db)
+        .await?;
+
+    for (link, source) in links {
+        if let Some(source) = source {
+            references
+                .entry(link.quote_id)
+                .or_default()
+                .push(to_response_reference(source));
+        }
+    }
+
+    Ok(references)
+}
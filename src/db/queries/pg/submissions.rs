@@ -0,0 +1,117 @@
+use crate::entities::quote_submissions::{self, Column, Entity as SubmissionEntity};
+use crate::models::data::{PageQuery, PagedResponse, SubmissionStatus};
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+pub async fn insert_submission(
+    db: &DatabaseConnection,
+    submission: quote_submissions::ActiveModel,
+) -> Result<quote_submissions::Model, AppError> {
+    Ok(SubmissionEntity::insert(submission)
+        .exec_with_returning(db)
+        .await?)
+}
+
+pub async fn fetch_submission<C: ConnectionTrait>(
+    db: &C,
+    submission_id: i32,
+) -> Result<quote_submissions::Model, AppError> {
+    SubmissionEntity::find_by_id(submission_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Submission {} not found", submission_id)))
+}
+
+/// Submissions oldest first, so the moderation queue is worked in order,
+/// optionally limited to one submitter or status.
+pub async fn fetch_submissions(
+    db: &DatabaseConnection,
+    submitted_by: Option<&str>,
+    status: Option<SubmissionStatus>,
+    page: &PageQuery,
+) -> Result<PagedResponse<quote_submissions::Model>, AppError> {
+    let mut query = SubmissionEntity::find();
+    if let Some(submitted_by) = submitted_by {
+        query = query.filter(Column::SubmittedBy.eq(submitted_by));
+    }
+    if let Some(status) = status {
+        query = query.filter(Column::Status.eq(status.as_str()));
+    }
+
+    let paginator = query.order_by_asc(Column::Id).paginate(db, page.per_page());
+    let total = paginator.num_items().await?;
+    let items = paginator.fetch_page(page.page() - 1).await?;
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+pub async fn update_submission(
+    db: &DatabaseConnection,
+    mut submission: quote_submissions::ActiveModel,
+) -> Result<quote_submissions::Model, AppError> {
+    submission.updated_at = Set(Utc::now().into());
+    Ok(submission.update(db).await?)
+}
+
+/// Moves a pending submission to `status`. Fails when another admin reviewed
+/// it first; inside a transaction the row stays claimed until commit.
+pub async fn mark_reviewed<C: ConnectionTrait>(
+    db: &C,
+    submission_id: i32,
+    status: SubmissionStatus,
+    rejection_reason: Option<String>,
+    quote_id: Option<i32>,
+    reviewed_by: Option<&str>,
+) -> Result<quote_submissions::Model, AppError> {
+    let now = Utc::now();
+    let result = SubmissionEntity::update_many()
+        .set(quote_submissions::ActiveModel {
+            status: Set(status.as_str().to_string()),
+            rejection_reason: Set(rejection_reason),
+            quote_id: Set(quote_id),
+            reviewed_by: Set(reviewed_by.map(str::to_string)),
+            reviewed_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        })
+        .filter(Column::Id.eq(submission_id))
+        .filter(Column::Status.eq(SubmissionStatus::Pending.as_str()))
+        .exec(db)
+        .await?;
+
+    match result.rows_affected {
+        0 => Err(AppError::BadRequest(format!(
+            "Submission {} is no longer pending",
+            submission_id
+        ))),
+        _ => fetch_submission(db, submission_id).await,
+    }
+}
+
+/// Records the quote an approved submission became.
+pub async fn set_submission_quote<C: ConnectionTrait>(
+    db: &C,
+    submission_id: i32,
+    quote_id: i32,
+) -> Result<quote_submissions::Model, AppError> {
+    SubmissionEntity::update_many()
+        .set(quote_submissions::ActiveModel {
+            quote_id: Set(Some(quote_id)),
+            ..Default::default()
+        })
+        .filter(Column::Id.eq(submission_id))
+        .exec(db)
+        .await?;
+    fetch_submission(db, submission_id).await
+}
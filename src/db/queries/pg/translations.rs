@@ -0,0 +1,420 @@
+use crate::entities::quote_category::{self, Entity as QuoteCategoryEntity};
+use crate::entities::quote_source::{self, Entity as QuoteSourceEntity};
+use crate::entities::quote_submissions::{self, Entity as SubmissionEntity};
+use crate::entities::quotes::{self, Column as QuoteColumn, Entity as QuoteEntity};
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::entities::translation_groups::{self, Entity as TranslationGroupEntity};
+use crate::helper::attribution;
+use crate::models::data::{
+    SeedTransliterationResponse, SeededQuote, SubmissionStatus, TranslationGroupResponse,
+    TranslationsResponse,
+};
+use crate::models::errors::AppError;
+
+use rand::seq::SliceRandom;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+
+use super::{find_live_quotes, to_response_quotes};
+
+pub async fn fetch_translations(
+    db: &DatabaseConnection,
+    quote_id: i32,
+) -> Result<TranslationsResponse, AppError> {
+    let quote = find_live_quotes()
+        .filter(QuoteColumn::Id.eq(quote_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+
+    let Some(group_id) = quote.translation_group_id else {
+        return Ok(TranslationsResponse {
+            quote_id,
+            translation_group_id: None,
+            original_quote_id: None,
+            translations: Vec::new(),
+        });
+    };
+
+    let group = TranslationGroupEntity::find_by_id(group_id).one(db).await?;
+
+    let members = find_live_quotes()
+        .filter(QuoteColumn::TranslationGroupId.eq(group_id))
+        .filter(QuoteColumn::Id.ne(quote_id))
+        .order_by_asc(QuoteColumn::Language)
+        .all(db)
+        .await?;
+
+    Ok(TranslationsResponse {
+        quote_id,
+        translation_group_id: Some(group_id),
+        original_quote_id: group.and_then(|group| group.original_quote_id),
+        translations: to_response_quotes(db, members).await?,
+    })
+}
+
+/// Picks a random translation group with an eligible quote in every requested
+/// language, returning the quote id to feature for each language.
+pub async fn pick_random_complete_group(
+    db: &DatabaseConnection,
+    languages: &[&str],
+    min_grade: &AttributionStatus,
+) -> Result<Option<HashMap<String, i32>>, AppError> {
+    let candidates = find_live_quotes()
+        .filter(QuoteColumn::TranslationGroupId.is_not_null())
+        .filter(QuoteColumn::Language.is_in(languages.iter().copied()))
+        .filter(QuoteColumn::AttributionStatus.is_in(attribution::statuses_at_least(min_grade)))
+        .all(db)
+        .await?;
+
+    let mut groups: HashMap<i32, HashMap<String, Vec<i32>>> = HashMap::new();
+    for quote in candidates {
+        if let Some(group_id) = quote.translation_group_id {
+            groups
+                .entry(group_id)
+                .or_default()
+                .entry(quote.language)
+                .or_default()
+                .push(quote.id);
+        }
+    }
+
+    let complete: Vec<HashMap<String, Vec<i32>>> = groups
+        .into_values()
+        .filter(|members| languages.iter().all(|lang| members.contains_key(*lang)))
+        .collect();
+
+    let mut rng = rand::thread_rng();
+    Ok(complete.choose(&mut rng).map(|members| {
+        members
+            .iter()
+            .filter_map(|(lang, ids)| ids.choose(&mut rng).map(|id| (lang.clone(), *id)))
+            .collect()
+    }))
+}
+
+pub async fn create_translation_group(
+    db: &DatabaseConnection,
+    original_quote_id: Option<i32>,
+    quote_ids: &[i32],
+) -> Result<TranslationGroupResponse, AppError> {
+    let mut quote_ids: Vec<i32> = quote_ids.to_vec();
+    quote_ids.extend(original_quote_id);
+    quote_ids.sort_unstable();
+    quote_ids.dedup();
+
+    if quote_ids.len() < 2 {
+        return Err(AppError::BadRequest(
+            "A translation group needs at least two quotes".to_string(),
+        ));
+    }
+
+    let members = find_live_quotes()
+        .filter(QuoteColumn::Id.is_in(quote_ids.clone()))
+        .all(db)
+        .await?;
+
+    if members.len() != quote_ids.len() {
+        let found: HashSet<i32> = members.iter().map(|quote| quote.id).collect();
+        let missing: Vec<String> = quote_ids
+            .iter()
+            .filter(|id| !found.contains(id))
+            .map(|id| id.to_string())
+            .collect();
+        return Err(AppError::NotFound(format!(
+            "Quotes not found: {}",
+            missing.join(", ")
+        )));
+    }
+
+    // Moving a quote out of its group could leave that group with a single
+    // member, so regrouping has to go through deleting the old group.
+    let grouped: Vec<String> = members
+        .iter()
+        .filter_map(|quote| {
+            quote
+                .translation_group_id
+                .map(|group_id| format!("{} (group {})", quote.id, group_id))
+        })
+        .collect();
+    if !grouped.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Quotes already belong to a translation group: {}",
+            grouped.join(", ")
+        )));
+    }
+
+    let mut languages = HashSet::new();
+    for quote in &members {
+        if !languages.insert(quote.language.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "A translation group can hold only one quote per language, '{}' appears twice",
+                quote.language
+            )));
+        }
+    }
+
+    let txn = db.begin().await?;
+
+    let group = TranslationGroupEntity::insert(translation_groups::ActiveModel {
+        original_quote_id: Set(original_quote_id),
+        ..Default::default()
+    })
+    .exec_with_returning(&txn)
+    .await?;
+
+    QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            translation_group_id: Set(Some(group.id)),
+            ..Default::default()
+        })
+        .filter(QuoteColumn::Id.is_in(quote_ids.clone()))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(TranslationGroupResponse {
+        id: group.id,
+        original_quote_id: group.original_quote_id,
+        quote_ids,
+    })
+}
+
+pub async fn delete_translation_group(
+    db: &DatabaseConnection,
+    group_id: i32,
+) -> Result<(), AppError> {
+    let txn = db.begin().await?;
+
+    QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            translation_group_id: Set(None),
+            ..Default::default()
+        })
+        .filter(QuoteColumn::TranslationGroupId.eq(group_id))
+        .exec(&txn)
+        .await?;
+
+    let result = TranslationGroupEntity::delete_by_id(group_id)
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound(format!(
+            "Translation group {} not found",
+            group_id
+        )));
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Links `quote_id`, just created from a submission, into the translation
+/// group of `original_id`, creating the group when the original has none, and
+/// copies the original's structured sources. Does nothing when the original
+/// is gone.
+pub async fn join_translation_group(
+    txn: &DatabaseTransaction,
+    original_id: i32,
+    quote_id: i32,
+) -> Result<Option<i32>, AppError> {
+    let Some(original) = find_live_quotes()
+        .filter(QuoteColumn::Id.eq(original_id))
+        .one(txn)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let quote = QuoteEntity::find_by_id(quote_id)
+        .one(txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+
+    let group_id = match original.translation_group_id {
+        Some(group_id) => {
+            let taken = QuoteEntity::find()
+                .filter(QuoteColumn::TranslationGroupId.eq(group_id))
+                .filter(QuoteColumn::Language.eq(quote.language.as_str()))
+                .one(txn)
+                .await?;
+            if let Some(taken) = taken {
+                return Err(AppError::BadRequest(format!(
+                    "Translation group {} already has a '{}' quote ({})",
+                    group_id, quote.language, taken.id
+                )));
+            }
+            group_id
+        }
+        None => {
+            let group = TranslationGroupEntity::insert(translation_groups::ActiveModel {
+                ..Default::default()
+            })
+            .exec_with_returning(txn)
+            .await?;
+
+            QuoteEntity::update_many()
+                .set(quotes::ActiveModel {
+                    translation_group_id: Set(Some(group.id)),
+                    ..Default::default()
+                })
+                .filter(QuoteColumn::Id.eq(original.id))
+                .exec(txn)
+                .await?;
+
+            group.id
+        }
+    };
+
+    QuoteEntity::update_many()
+        .set(quotes::ActiveModel {
+            translation_group_id: Set(Some(group_id)),
+            ..Default::default()
+        })
+        .filter(QuoteColumn::Id.eq(quote_id))
+        .exec(txn)
+        .await?;
+
+    let sources: Vec<quote_source::ActiveModel> = QuoteSourceEntity::find()
+        .filter(quote_source::Column::QuoteId.eq(original.id))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| quote_source::ActiveModel {
+            quote_id: Set(quote_id),
+            source_id: Set(link.source_id),
+            position: Set(link.position),
+        })
+        .collect();
+
+    if !sources.is_empty() {
+        QuoteSourceEntity::insert_many(sources).exec(txn).await?;
+    }
+
+    Ok(Some(group_id))
+}
+
+/// Proposes a `to_language` copy of every `from_language` quote whose
+/// translation group has no `to_language` member yet, deleted ones included,
+/// converting the text with `convert`. Machine conversion is lossy, so each
+/// copy becomes a pending submission by `submitted_by` that an admin reviews;
+/// approving it links the quote into the original's translation group.
+/// Quotes that already have a pending proposal are skipped.
+pub async fn seed_converted_quotes(
+    db: &DatabaseConnection,
+    from_language: &str,
+    to_language: &str,
+    convert: fn(&str) -> String,
+    submitted_by: &str,
+    dry_run: bool,
+) -> Result<SeedTransliterationResponse, AppError> {
+    let originals = find_live_quotes()
+        .filter(QuoteColumn::Language.eq(from_language))
+        .order_by_asc(QuoteColumn::Id)
+        .all(db)
+        .await?;
+
+    // Soft-deleted members still hold their group's slot in
+    // quotes_translation_group_language_idx, so they count as covering it.
+    let covered_groups: HashSet<i32> = QuoteEntity::find()
+        .filter(QuoteColumn::Language.eq(to_language))
+        .filter(QuoteColumn::TranslationGroupId.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|quote| quote.translation_group_id)
+        .collect();
+
+    let proposed: HashSet<i32> = SubmissionEntity::find()
+        .select_only()
+        .column(quote_submissions::Column::TranslationOf)
+        .filter(quote_submissions::Column::Language.eq(to_language))
+        .filter(quote_submissions::Column::Status.eq(SubmissionStatus::Pending.as_str()))
+        .filter(quote_submissions::Column::TranslationOf.is_not_null())
+        .into_tuple::<Option<i32>>()
+        .all(db)
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let ids: Vec<i32> = originals.iter().map(|quote| quote.id).collect();
+    let mut categories: HashMap<i32, Vec<i32>> = HashMap::new();
+    for link in QuoteCategoryEntity::find()
+        .filter(quote_category::Column::QuoteId.is_in(ids))
+        .order_by_asc(quote_category::Column::CategoryId)
+        .all(db)
+        .await?
+    {
+        categories
+            .entry(link.quote_id)
+            .or_default()
+            .push(link.category_id);
+    }
+
+    let scanned = originals.len();
+    let mut skipped = 0;
+    let mut created = Vec::new();
+    let txn = db.begin().await?;
+
+    for original in originals {
+        if proposed.contains(&original.id)
+            || original
+                .translation_group_id
+                .is_some_and(|group_id| covered_groups.contains(&group_id))
+        {
+            skipped += 1;
+            continue;
+        }
+
+        let content = convert(&original.quote);
+
+        if dry_run {
+            created.push(SeededQuote {
+                source_quote_id: original.id,
+                submission_id: None,
+                translation_group_id: original.translation_group_id,
+                content,
+            });
+            continue;
+        }
+
+        let submission = SubmissionEntity::insert(quote_submissions::ActiveModel {
+            submitted_by: Set(submitted_by.to_string()),
+            content: Set(content.clone()),
+            author: Set(original.author.clone()),
+            reference: Set(original.reference.clone()),
+            language: Set(to_language.to_string()),
+            category_ids: Set(categories.remove(&original.id).unwrap_or_default()),
+            notes: Set(Some(format!(
+                "Machine transliteration of quote {}; check spelling and vowels before approving.",
+                original.id
+            ))),
+            translation_of: Set(Some(original.id)),
+            ..Default::default()
+        })
+        .exec_with_returning(&txn)
+        .await?;
+
+        created.push(SeededQuote {
+            source_quote_id: original.id,
+            submission_id: Some(submission.id),
+            translation_group_id: original.translation_group_id,
+            content,
+        });
+    }
+
+    txn.commit().await?;
+
+    Ok(SeedTransliterationResponse {
+        dry_run,
+        scanned,
+        skipped,
+        created,
+    })
+}
@@ -1,4 +1,4 @@
-use crate::models::data::ResponseQuote;
+use crate::models::data::{BundleMeta, ResponseQuote};
 use crate::models::errors::AppError;
 use log::info;
 
@@ -16,7 +16,7 @@ pub async fn update_qotd_into_redis(
     let mut conn = redis
         .get_async_connection()
         .await
-        .map_err(|err| AppError::RedisError(err))?;
+        .map_err(AppError::RedisError)?;
 
     update_qotd_in_redis(&mut conn, quote).await?;
     update_qotd_reset_time_in_redis(&mut conn, quote).await?;
@@ -37,7 +37,7 @@ pub async fn update_qotd_in_redis(
     let _: () = conn
         .set(key, quote_json)
         .await
-        .map_err(|err| AppError::RedisError(err))?;
+        .map_err(AppError::RedisError)?;
     Ok(())
 }
 
@@ -77,3 +77,68 @@ pub async fn fetch_last_qotd_timestamp(
         Err(e) => Err(AppError::RedisError(e)),
     }
 }
+
+/// Drops the cached quote of the day so the next request reloads it from the
+/// database.
+pub async fn invalidate_qotd(redis_client: &redis::Client, lang: &str) -> Result<(), AppError> {
+    let mut conn = redis_client.get_async_connection().await?;
+    let _: () = conn.del(format!("qotd:{}", lang)).await?;
+    Ok(())
+}
+
+pub async fn fetch_bundle(
+    redis_client: &redis::Client,
+    lang: &str,
+) -> Result<Option<(BundleMeta, Vec<u8>)>, AppError> {
+    let mut conn = redis_client.get_async_connection().await?;
+
+    let meta: Option<String> = conn.get(format!("bundle:{}:meta", lang)).await?;
+    let Some(meta) = meta else {
+        return Ok(None);
+    };
+    let body: Option<Vec<u8>> = conn.get(format!("bundle:{}", lang)).await?;
+
+    Ok(body
+        .map(|body| serde_json::from_str(&meta).map(|meta| (meta, body)))
+        .transpose()?)
+}
+
+pub async fn store_bundle(
+    redis_client: &redis::Client,
+    meta: &BundleMeta,
+    body: &[u8],
+) -> Result<(), AppError> {
+    let mut conn = redis_client.get_async_connection().await?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .set(format!("bundle:{}", meta.language), body)
+        .set(
+            format!("bundle:{}:meta", meta.language),
+            serde_json::to_string(meta)?,
+        )
+        .query_async(&mut conn)
+        .await?;
+    Ok(())
+}
+
+/// Counts a correction report against `key_id` in the current fixed window
+/// and returns the window's total so far.
+pub async fn increment_report_count(
+    redis_client: &redis::Client,
+    key_id: &str,
+    window_seconds: u64,
+) -> Result<u64, AppError> {
+    let mut conn = redis_client.get_async_connection().await?;
+    let window = Utc::now().timestamp() as u64 / window_seconds;
+    let key = format!("reports:{}:{}", key_id, window);
+
+    let (count,): (u64,) = redis::pipe()
+        .atomic()
+        .incr(&key, 1)
+        .expire(&key, window_seconds as usize)
+        .ignore()
+        .query_async(&mut conn)
+        .await?;
+    Ok(count)
+}
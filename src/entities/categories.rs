@@ -9,14 +9,33 @@ pub struct Model {
     pub id: i32,
     #[sea_orm(unique)]
     pub name: String,
+    #[sea_orm(unique)]
+    pub slug: String,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::category_names::Entity")]
+    CategoryNames,
     #[sea_orm(has_many = "super::quote_category::Entity")]
     QuoteCategory,
 }
 
+impl Related<super::category_names::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CategoryNames.def()
+    }
+}
+
 impl Related<super::quote_category::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::QuoteCategory.def()
@@ -1,8 +1,26 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+#[allow(unused_imports)]
 pub mod prelude;
 
+pub mod author_aliases;
+pub mod author_merge_candidates;
+pub mod author_names;
+pub mod authors;
 pub mod categories;
+pub mod category_names;
+pub mod favorites;
+pub mod languages;
+pub mod qotd_history;
 pub mod quote_category;
+pub mod quote_changes;
 pub mod quote_of_the_day;
+pub mod quote_ratings;
+pub mod quote_reports;
+pub mod quote_revisions;
+pub mod quote_source;
+pub mod quote_submissions;
 pub mod quotes;
+pub mod sea_orm_active_enums;
+pub mod sources;
+pub mod translation_groups;
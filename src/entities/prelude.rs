@@ -1,6 +1,22 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+pub use super::author_aliases::Entity as AuthorAliases;
+pub use super::author_merge_candidates::Entity as AuthorMergeCandidates;
+pub use super::author_names::Entity as AuthorNames;
+pub use super::authors::Entity as Authors;
 pub use super::categories::Entity as Categories;
+pub use super::category_names::Entity as CategoryNames;
+pub use super::favorites::Entity as Favorites;
+pub use super::languages::Entity as Languages;
+pub use super::qotd_history::Entity as QotdHistory;
 pub use super::quote_category::Entity as QuoteCategory;
+pub use super::quote_changes::Entity as QuoteChanges;
 pub use super::quote_of_the_day::Entity as QuoteOfTheDay;
+pub use super::quote_ratings::Entity as QuoteRatings;
+pub use super::quote_reports::Entity as QuoteReports;
+pub use super::quote_revisions::Entity as QuoteRevisions;
+pub use super::quote_source::Entity as QuoteSource;
+pub use super::quote_submissions::Entity as QuoteSubmissions;
 pub use super::quotes::Entity as Quotes;
+pub use super::sources::Entity as Sources;
+pub use super::translation_groups::Entity as TranslationGroups;
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.6
 
+use super::sea_orm_active_enums::AttributionStatus;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -13,14 +14,90 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub reference: Option<String>,
     pub language: String,
+    pub author_id: Option<i32>,
+    pub attribution_status: AttributionStatus,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub grading_notes: Option<String>,
+    pub graded_by: Option<String>,
+    pub translation_group_id: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub search_text: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub change_seq: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::authors::Entity",
+        from = "Column::AuthorId",
+        to = "super::authors::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Authors,
+    #[sea_orm(has_many = "super::favorites::Entity")]
+    Favorites,
+    #[sea_orm(
+        belongs_to = "super::languages::Entity",
+        from = "Column::Language",
+        to = "super::languages::Column::Code",
+        on_update = "Cascade",
+        on_delete = "NoAction"
+    )]
+    Languages,
+    #[sea_orm(has_many = "super::qotd_history::Entity")]
+    QotdHistory,
     #[sea_orm(has_many = "super::quote_category::Entity")]
     QuoteCategory,
+    #[sea_orm(has_many = "super::quote_changes::Entity")]
+    QuoteChanges,
     #[sea_orm(has_many = "super::quote_of_the_day::Entity")]
     QuoteOfTheDay,
+    #[sea_orm(has_many = "super::quote_ratings::Entity")]
+    QuoteRatings,
+    #[sea_orm(has_many = "super::quote_reports::Entity")]
+    QuoteReports,
+    #[sea_orm(has_many = "super::quote_revisions::Entity")]
+    QuoteRevisions,
+    #[sea_orm(has_many = "super::quote_source::Entity")]
+    QuoteSource,
+    #[sea_orm(has_many = "super::quote_submissions::Entity")]
+    QuoteSubmissions,
+    #[sea_orm(
+        belongs_to = "super::translation_groups::Entity",
+        from = "Column::TranslationGroupId",
+        to = "super::translation_groups::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    TranslationGroups,
+}
+
+impl Related<super::authors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Authors.def()
+    }
+}
+
+impl Related<super::favorites::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Favorites.def()
+    }
+}
+
+impl Related<super::languages::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Languages.def()
+    }
+}
+
+impl Related<super::qotd_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QotdHistory.def()
+    }
 }
 
 impl Related<super::quote_category::Entity> for Entity {
@@ -29,12 +106,54 @@ impl Related<super::quote_category::Entity> for Entity {
     }
 }
 
+impl Related<super::quote_changes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteChanges.def()
+    }
+}
+
 impl Related<super::quote_of_the_day::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::QuoteOfTheDay.def()
     }
 }
 
+impl Related<super::quote_ratings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteRatings.def()
+    }
+}
+
+impl Related<super::quote_reports::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteReports.def()
+    }
+}
+
+impl Related<super::quote_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteRevisions.def()
+    }
+}
+
+impl Related<super::quote_source::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteSource.def()
+    }
+}
+
+impl Related<super::quote_submissions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteSubmissions.def()
+    }
+}
+
+impl Related<super::translation_groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TranslationGroups.def()
+    }
+}
+
 impl Related<super::categories::Entity> for Entity {
     fn to() -> RelationDef {
         super::quote_category::Relation::Categories.def()
@@ -44,4 +163,13 @@ impl Related<super::categories::Entity> for Entity {
     }
 }
 
+impl Related<super::sources::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::quote_source::Relation::Sources.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::quote_source::Relation::Quotes.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,368 @@
+use crate::db::queries::{pg, rds};
+use crate::entities::quotes;
+use crate::helper::lint::{self, LintInput};
+use crate::helper::{attribution, dedup, languages};
+use crate::models::data::{
+    LintIssue, QuoteRequest, QuoteUpdateRequest, ResponseQuote, ResponseRevision, RevisionAction,
+};
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use log::{error, info};
+use rand::seq::SliceRandom;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, QueryFilter, TransactionTrait,
+};
+
+/// Findings about a written quote that did not block the write.
+#[derive(Debug, Default)]
+pub struct WriteWarnings {
+    pub possible_duplicates: Vec<i32>,
+    pub lint: Vec<LintIssue>,
+}
+
+/// Trims `value`, rejecting it when nothing is left.
+pub fn required(field: &str, value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    match value.is_empty() {
+        true => Err(AppError::BadRequest(format!("{} must not be empty", field))),
+        false => Ok(value.to_string()),
+    }
+}
+
+/// Trims `value`, treating a blank string as absent.
+pub fn optional(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Points the quote of the day for `lang` at another eligible quote, or clears
+/// it when none is left.
+async fn reassign_qotd<C: ConnectionTrait>(
+    conn: &C,
+    lang: &str,
+    excluded_id: i32,
+) -> Result<(), AppError> {
+    let min_grade = attribution::qotd_min_attribution();
+    let candidates: Vec<i32> = pg::fetch_ids_by_language(conn, lang, Some(&min_grade))
+        .await?
+        .into_iter()
+        .filter(|id| *id != excluded_id)
+        .collect();
+
+    let replacement = candidates.choose(&mut rand::thread_rng()).copied();
+    match replacement {
+        Some(quote_id) => pg::set_qotd(conn, lang, quote_id).await,
+        None => pg::admin_quotes::delete_qotd(conn, lang).await,
+    }
+}
+
+/// The database write has already happened, so a Redis failure is logged
+/// rather than reported as a failed request.
+pub async fn invalidate_cached_qotd(redis_client: &redis::Client, lang: &str) {
+    match rds::invalidate_qotd(redis_client, lang).await {
+        Ok(()) => info!("Invalidated cached QOTD for '{}'", lang),
+        Err(e) => error!("Failed to invalidate cached QOTD for '{}': {}", lang, e),
+    }
+}
+
+/// Validates, lints and dedup-checks a new quote, returning the row to
+/// insert and the findings that do not block it.
+pub async fn prepare_quote(
+    db: &DatabaseConnection,
+    request: &QuoteRequest,
+) -> Result<(quotes::ActiveModel, WriteWarnings), AppError> {
+    let content = required("content", &request.content)?;
+    let author = required("author", &request.author)?;
+    let reference = required("reference", &request.reference)?;
+    let language = languages::validate_language(db, &request.language).await?;
+    let attribution_status = request
+        .attribution_status
+        .as_deref()
+        .map(attribution::parse_attribution_status)
+        .transpose()?
+        .unwrap_or_default();
+
+    if let Some(author_id) = request.author_id {
+        pg::admin_quotes::ensure_author_exists(db, author_id).await?;
+    }
+    pg::admin_quotes::ensure_categories_exist(db, &request.category_ids).await?;
+    let script = languages::language_script(db, &language).await?;
+    let lint = lint::check(&LintInput {
+        content: &content,
+        author: &author,
+        reference: Some(&reference),
+        has_sources: false,
+        script: &script,
+    })?;
+    let possible_duplicates = dedup::check_new_quote(db, &language, &content).await?;
+
+    Ok((
+        quotes::ActiveModel {
+            quote: Set(content),
+            author: Set(author),
+            author_id: Set(request.author_id),
+            reference: Set(Some(reference)),
+            language: Set(language),
+            attribution_status: Set(attribution_status),
+            grading_notes: Set(request.grading_notes.clone()),
+            graded_by: Set(request.graded_by.clone()),
+            ..Default::default()
+        },
+        WriteWarnings {
+            possible_duplicates,
+            lint,
+        },
+    ))
+}
+
+/// Creates a quote after linting it and checking it for near-duplicates.
+pub async fn create_quote(
+    db: &DatabaseConnection,
+    request: &QuoteRequest,
+    changed_by: Option<&str>,
+) -> Result<(ResponseQuote, WriteWarnings), AppError> {
+    let (quote, warnings) = prepare_quote(db, request).await?;
+
+    let txn = db.begin().await?;
+    let quote =
+        pg::admin_quotes::insert_quote(&txn, quote, &request.category_ids, changed_by).await?;
+    txn.commit().await?;
+
+    Ok((pg::to_response_quote(db, quote).await?, warnings))
+}
+
+/// Checks an update against the live quote, returning the changed row and
+/// its lint findings.
+pub async fn prepare_update(
+    db: &DatabaseConnection,
+    quote_id: i32,
+    request: &QuoteUpdateRequest,
+) -> Result<(quotes::ActiveModel, Vec<LintIssue>), AppError> {
+    let existing = pg::find_live_quotes()
+        .filter(quotes::Column::Id.eq(quote_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+    let mut quote = existing.into_active_model();
+
+    if let Some(content) = &request.content {
+        quote.quote = Set(required("content", content)?);
+    }
+    if let Some(author) = &request.author {
+        quote.author = Set(required("author", author)?);
+    }
+    if let Some(reference) = &request.reference {
+        quote.reference = Set(Some(required("reference", reference)?));
+    }
+    if let Some(language) = &request.language {
+        quote.language = Set(languages::validate_language(db, language).await?);
+    }
+    if let Some(status) = &request.attribution_status {
+        quote.attribution_status = Set(attribution::parse_attribution_status(status)?);
+    }
+    if let Some(author_id) = request.author_id {
+        pg::admin_quotes::ensure_author_exists(db, author_id).await?;
+        quote.author_id = Set(Some(author_id));
+    }
+    if request.grading_notes.is_some() {
+        quote.grading_notes = Set(request.grading_notes.clone());
+    }
+    if request.graded_by.is_some() {
+        quote.graded_by = Set(request.graded_by.clone());
+    }
+    if let Some(category_ids) = &request.category_ids {
+        pg::admin_quotes::ensure_categories_exist(db, category_ids).await?;
+    }
+
+    let has_sources = !pg::lint::fetch_ids_with_sources(db, &[quote_id])
+        .await?
+        .is_empty();
+    let script = languages::language_script(db, quote.language.as_ref()).await?;
+    let lint = lint::check(&LintInput {
+        content: quote.quote.as_ref(),
+        author: quote.author.as_ref(),
+        reference: quote.reference.as_ref().as_deref(),
+        has_sources,
+        script: &script,
+    })?;
+
+    Ok((quote, lint))
+}
+
+/// Writes a prepared update inside `txn` and moves the quote of the day off
+/// the quote where it no longer qualifies. Returns the languages whose cached
+/// QOTD must be dropped once `txn` commits.
+pub async fn apply_update(
+    txn: &DatabaseTransaction,
+    quote: quotes::ActiveModel,
+    category_ids: Option<&[i32]>,
+    changed_by: Option<&str>,
+) -> Result<(quotes::Model, Vec<String>), AppError> {
+    let quote = pg::admin_quotes::update_quote(
+        txn,
+        quote,
+        category_ids,
+        RevisionAction::Update,
+        changed_by,
+    )
+    .await?;
+
+    // A quote that moved language or dropped below the QOTD grade must not
+    // stay featured.
+    let eligible = attribution::statuses_at_least(&attribution::qotd_min_attribution())
+        .contains(&quote.attribution_status);
+    let languages = pg::admin_quotes::fetch_qotd_languages_for_quote(txn, quote.id).await?;
+    for lang in &languages {
+        if !(eligible && *lang == quote.language) {
+            reassign_qotd(txn, lang, quote.id).await?;
+        }
+    }
+
+    Ok((quote, languages))
+}
+
+pub async fn update_quote(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    quote_id: i32,
+    request: &QuoteUpdateRequest,
+    changed_by: Option<&str>,
+) -> Result<(ResponseQuote, WriteWarnings), AppError> {
+    let (quote, lint) = prepare_update(db, quote_id, request).await?;
+
+    let txn = db.begin().await?;
+    let (quote, languages) =
+        apply_update(&txn, quote, request.category_ids.as_deref(), changed_by).await?;
+    txn.commit().await?;
+
+    for lang in languages {
+        invalidate_cached_qotd(redis_client, &lang).await;
+    }
+
+    Ok((
+        pg::to_response_quote(db, quote).await?,
+        WriteWarnings {
+            lint,
+            ..Default::default()
+        },
+    ))
+}
+
+pub async fn delete_quote(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    quote_id: i32,
+    changed_by: Option<&str>,
+) -> Result<(), AppError> {
+    let txn = db.begin().await?;
+    pg::admin_quotes::delete_quote(&txn, quote_id, changed_by).await?;
+
+    // A deleted quote must not stay featured, so move every QOTD off it in the
+    // same transaction and only drop the cached copies once that committed.
+    let qotd_languages = pg::admin_quotes::fetch_qotd_languages_for_quote(&txn, quote_id).await?;
+    for lang in &qotd_languages {
+        reassign_qotd(&txn, lang, quote_id).await?;
+    }
+    txn.commit().await?;
+
+    for lang in &qotd_languages {
+        invalidate_cached_qotd(redis_client, lang).await;
+    }
+    Ok(())
+}
+
+pub async fn restore_quote(
+    db: &DatabaseConnection,
+    quote_id: i32,
+    changed_by: Option<&str>,
+) -> Result<ResponseQuote, AppError> {
+    let quote = pg::admin_quotes::restore_quote(db, quote_id, changed_by).await?;
+    pg::to_response_quote(db, quote).await
+}
+
+pub async fn list_revisions(
+    db: &DatabaseConnection,
+    quote_id: i32,
+) -> Result<Vec<ResponseRevision>, AppError> {
+    let revisions = pg::revisions::fetch_revisions(db, quote_id).await?;
+    if revisions.is_empty() {
+        return Err(AppError::NotFound(format!("Quote {} not found", quote_id)));
+    }
+
+    Ok(revisions
+        .into_iter()
+        .map(|revision| ResponseRevision {
+            revision: revision.revision,
+            action: revision.action,
+            content: revision.content,
+            author: revision.author,
+            author_id: revision.author_id,
+            reference: revision.reference,
+            category_ids: revision.category_ids,
+            changed_by: revision.changed_by,
+            changed_at: revision.changed_at.with_timezone(&Utc),
+        })
+        .collect())
+}
+
+/// Puts a quote's text, author, reference and categories back to how they
+/// were at `revision`, recorded as a new revision. Authors and categories
+/// deleted since are dropped.
+pub async fn revert_quote(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    quote_id: i32,
+    revision: i32,
+    changed_by: Option<&str>,
+) -> Result<ResponseQuote, AppError> {
+    let revision = pg::revisions::fetch_revision(db, quote_id, revision).await?;
+    let existing = quotes::Entity::find_by_id(quote_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+    if existing.deleted_at.is_some() {
+        return Err(AppError::BadRequest(format!(
+            "Quote {} is deleted, restore it before reverting",
+            quote_id
+        )));
+    }
+
+    let author_id = match revision.author_id {
+        Some(author_id) => match pg::admin_quotes::ensure_author_exists(db, author_id).await {
+            Ok(()) => Some(author_id),
+            Err(AppError::BadRequest(_)) => None,
+            Err(e) => return Err(e),
+        },
+        None => None,
+    };
+    let category_ids =
+        pg::admin_quotes::fetch_existing_category_ids(db, &revision.category_ids).await?;
+
+    let mut quote = existing.into_active_model();
+    quote.quote = Set(revision.content);
+    quote.author = Set(revision.author);
+    quote.author_id = Set(author_id);
+    quote.reference = Set(revision.reference);
+
+    let txn = db.begin().await?;
+    let quote = pg::admin_quotes::update_quote(
+        &txn,
+        quote,
+        Some(&category_ids),
+        RevisionAction::Revert,
+        changed_by,
+    )
+    .await?;
+    txn.commit().await?;
+
+    for lang in pg::admin_quotes::fetch_qotd_languages_for_quote(db, quote_id).await? {
+        invalidate_cached_qotd(redis_client, &lang).await;
+    }
+
+    pg::to_response_quote(db, quote).await
+}
@@ -0,0 +1,155 @@
+const TATWEEL: char = '\u{0640}';
+
+/// Harakat, tanween, shadda, sukun, superscript alef and Quranic annotation
+/// marks.
+pub fn is_diacritic(c: char) -> bool {
+    matches!(c, '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}' | '\u{06D6}'..='\u{06ED}')
+}
+
+/// Letters of the Arabic blocks, including the supplement, extended and
+/// presentation forms.
+pub fn is_arabic_letter(c: char) -> bool {
+    matches!(c, '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' | '\u{08A0}'..='\u{08FF}' | '\u{FB50}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}')
+        && c.is_alphabetic()
+}
+
+pub fn strip_diacritics(text: &str) -> String {
+    text.chars()
+        .filter(|c| !is_diacritic(*c) && *c != TATWEEL)
+        .collect()
+}
+
+fn fold_letter(c: char) -> char {
+    match c {
+        'أ' | 'إ' | 'آ' | 'ٱ' => 'ا',
+        'ى' => 'ي',
+        'ة' => 'ه',
+        'ؤ' => 'و',
+        'ئ' => 'ي',
+        other => other,
+    }
+}
+
+/// Normalises text for comparison: drops diacritics and tatweel, folds alef,
+/// yaa and taa marbuta variants, lowercases and collapses whitespace. Keep in
+/// sync with the `quotes.search_text` generated column.
+pub fn normalize(text: &str) -> String {
+    strip_diacritics(text)
+        .chars()
+        .map(fold_letter)
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_TEXT_MIGRATION: &str =
+        include_str!("../../migrations/008_add_quote_search_text.sql");
+
+    /// Single-quoted literals of the `search_text` expression, in order.
+    fn sql_literals() -> Vec<&'static str> {
+        let expression = &SEARCH_TEXT_MIGRATION[SEARCH_TEXT_MIGRATION
+            .find("GENERATED ALWAYS")
+            .expect("search_text column")..];
+        expression.split('\'').skip(1).step_by(2).collect()
+    }
+
+    fn parse_unicode_escape(chars: &mut std::str::Chars) -> char {
+        assert_eq!(chars.next(), Some('u'));
+        let hex: String = chars.take(4).collect();
+        char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap()
+    }
+
+    /// Parses a `[\uXXXX-\uXXXX\uXXXX...]` bracket expression.
+    fn sql_class(pattern: &str) -> Vec<(char, char)> {
+        let inner = pattern
+            .strip_prefix('[')
+            .unwrap()
+            .strip_suffix(']')
+            .unwrap();
+        let mut chars = inner.chars();
+        let mut ranges = Vec::new();
+        while let Some(c) = chars.next() {
+            assert_eq!(c, '\\');
+            let start = parse_unicode_escape(&mut chars);
+            let mut lookahead = chars.clone();
+            let end = match lookahead.next() {
+                Some('-') => {
+                    chars.next();
+                    assert_eq!(chars.next(), Some('\\'));
+                    parse_unicode_escape(&mut chars)
+                }
+                _ => start,
+            };
+            ranges.push((start, end));
+        }
+        ranges
+    }
+
+    #[test]
+    fn stripped_characters_match_search_text_column() {
+        let literals = sql_literals();
+        let class = sql_class(literals[0]);
+        for c in '\u{0600}'..='\u{06FF}' {
+            let in_sql = class
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&c));
+            let in_rust = is_diacritic(c) || c == TATWEEL;
+            assert_eq!(in_sql, in_rust, "U+{:04X}", c as u32);
+        }
+    }
+
+    #[test]
+    fn folded_letters_match_search_text_column() {
+        let literals = sql_literals();
+        let from: Vec<char> = literals[3].chars().collect();
+        let to: Vec<char> = literals[4].chars().collect();
+        assert_eq!(from.len(), to.len());
+
+        for c in '\u{0600}'..='\u{06FF}' {
+            let expected = from
+                .iter()
+                .position(|f| *f == c)
+                .map(|index| to[index])
+                .unwrap_or(c);
+            assert_eq!(fold_letter(c), expected, "U+{:04X}", c as u32);
+        }
+    }
+
+    #[test]
+    fn normalize_drops_diacritics_and_tatweel() {
+        assert_eq!(normalize("العِلْمُ"), "العلم");
+        assert_eq!(normalize("الـــعلم"), "العلم");
+    }
+
+    #[test]
+    fn normalize_folds_letter_variants() {
+        assert_eq!(normalize("إيمان"), normalize("ايمان"));
+        assert_eq!(normalize("مدرسة"), "مدرسه");
+        assert_eq!(normalize("على"), "علي");
+    }
+
+    #[test]
+    fn normalize_lowercases_and_collapses_whitespace() {
+        assert_eq!(normalize("  Seek\tKNOWLEDGE \n now "), "seek knowledge now");
+    }
+
+    #[test]
+    fn strip_diacritics_keeps_letters() {
+        assert_eq!(strip_diacritics("بِسْمِ اللَّهِ"), "بسم الله");
+    }
+
+    #[test]
+    fn is_arabic_letter_excludes_digits_and_punctuation() {
+        assert!(is_arabic_letter('ع'));
+        assert!(is_arabic_letter('ﻻ'));
+        assert!(!is_arabic_letter('٣'));
+        assert!(!is_arabic_letter('،'));
+        assert!(!is_arabic_letter('a'));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::models::errors::AppError;
+use crate::utils;
+use log::error;
+use sea_orm::ActiveEnum;
+
+/// Attribution grades from most to least trustworthy.
+const GRADE_ORDER: [AttributionStatus; 5] = [
+    AttributionStatus::Verified,
+    AttributionStatus::Weak,
+    AttributionStatus::Unknown,
+    AttributionStatus::Disputed,
+    AttributionStatus::Misattributed,
+];
+
+pub fn parse_attribution_status(value: &str) -> Result<AttributionStatus, AppError> {
+    GRADE_ORDER
+        .iter()
+        .find(|status| status.to_value() == value.to_ascii_lowercase())
+        .cloned()
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown attribution status '{}', expected one of verified, weak, unknown, disputed, misattributed",
+                value
+            ))
+        })
+}
+
+pub fn statuses_at_least(min_grade: &AttributionStatus) -> Vec<AttributionStatus> {
+    GRADE_ORDER
+        .iter()
+        .take_while(|status| *status != min_grade)
+        .chain(std::iter::once(min_grade))
+        .cloned()
+        .collect()
+}
+
+/// Minimum grade a quote needs to be featured as the quote of the day,
+/// configurable through `QOTD_MIN_ATTRIBUTION` and defaulting to `unknown`
+/// so disputed and misattributed sayings are never featured. An unparseable
+/// value is logged and the default applies.
+pub fn qotd_min_attribution() -> AttributionStatus {
+    let Ok(value) = utils::env::load_env_var("QOTD_MIN_ATTRIBUTION") else {
+        return AttributionStatus::Unknown;
+    };
+
+    parse_attribution_status(&value).unwrap_or_else(|e| {
+        error!("Ignoring QOTD_MIN_ATTRIBUTION: {}", e);
+        AttributionStatus::Unknown
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_at_least_includes_the_minimum_and_better() {
+        assert_eq!(
+            statuses_at_least(&AttributionStatus::Verified),
+            vec![AttributionStatus::Verified]
+        );
+        assert_eq!(
+            statuses_at_least(&AttributionStatus::Unknown),
+            vec![
+                AttributionStatus::Verified,
+                AttributionStatus::Weak,
+                AttributionStatus::Unknown,
+            ]
+        );
+        assert_eq!(
+            statuses_at_least(&AttributionStatus::Misattributed),
+            GRADE_ORDER.to_vec()
+        );
+    }
+
+    #[test]
+    fn statuses_at_least_unknown_excludes_disputed() {
+        let eligible = statuses_at_least(&AttributionStatus::Unknown);
+        assert!(!eligible.contains(&AttributionStatus::Disputed));
+        assert!(!eligible.contains(&AttributionStatus::Misattributed));
+    }
+
+    #[test]
+    fn parse_attribution_status_is_case_insensitive() {
+        assert_eq!(
+            parse_attribution_status("Disputed").unwrap(),
+            AttributionStatus::Disputed
+        );
+        assert!(parse_attribution_status("sahih").is_err());
+    }
+}
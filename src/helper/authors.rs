@@ -0,0 +1,57 @@
+use crate::db::queries::pg;
+use crate::models::data::{
+    AuthorMergeGroup, AuthorMergeMember, AuthorMergeRequest, AuthorMergeResponse,
+};
+use crate::models::errors::AppError;
+
+use sea_orm::DatabaseConnection;
+
+/// Groups of authors that may be spellings of one person. Nothing is merged;
+/// the list is for an admin to review.
+pub async fn merge_candidates(db: &DatabaseConnection) -> Result<Vec<AuthorMergeGroup>, AppError> {
+    let candidates = pg::authors::fetch_merge_candidates(db).await?;
+    let ids: Vec<i32> = candidates
+        .iter()
+        .map(|candidate| candidate.author_id)
+        .collect();
+    let counts = pg::authors::fetch_quote_counts(db, &ids).await?;
+
+    let mut groups: Vec<AuthorMergeGroup> = Vec::new();
+    for candidate in candidates {
+        let member = AuthorMergeMember {
+            id: candidate.author_id,
+            name: candidate.name,
+            quotes: counts.get(&candidate.author_id).copied().unwrap_or(0),
+        };
+        match groups.last_mut() {
+            Some(group) if group.key == candidate.merge_key => group.authors.push(member),
+            _ => groups.push(AuthorMergeGroup {
+                key: candidate.merge_key,
+                authors: vec![member],
+            }),
+        }
+    }
+
+    Ok(groups)
+}
+
+pub async fn merge_author(
+    db: &DatabaseConnection,
+    source_id: i32,
+    request: &AuthorMergeRequest,
+    changed_by: Option<&str>,
+) -> Result<AuthorMergeResponse, AppError> {
+    if source_id == request.into {
+        return Err(AppError::BadRequest(
+            "An author cannot be merged into itself".to_string(),
+        ));
+    }
+
+    let quotes_moved = pg::authors::merge_authors(db, source_id, request.into, changed_by).await?;
+
+    Ok(AuthorMergeResponse {
+        merged_id: source_id,
+        into: pg::authors::fetch_author_by_id(db, request.into).await?,
+        quotes_moved,
+    })
+}
@@ -0,0 +1,71 @@
+use crate::db::queries::{pg, rds};
+use crate::models::data::{BundleMeta, QuoteBundle};
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::{error, info};
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+
+async fn build_bundle(
+    db: &DatabaseConnection,
+    language: &str,
+    cursor: i64,
+) -> Result<(BundleMeta, Vec<u8>), AppError> {
+    let quotes = pg::changes::fetch_language_quotes(db, language).await?;
+    let bundle = QuoteBundle {
+        language: language.to_string(),
+        cursor,
+        generated_at: Utc::now(),
+        count: quotes.len(),
+        quotes,
+    };
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    serde_json::to_writer(&mut encoder, &bundle)?;
+    encoder.flush()?;
+    let body = encoder.finish()?;
+
+    let meta = BundleMeta {
+        language: bundle.language,
+        cursor,
+        sha256: format!("{:x}", Sha256::digest(&body)),
+        size: body.len(),
+        count: bundle.count,
+        generated_at: bundle.generated_at,
+    };
+    Ok((meta, body))
+}
+
+/// Returns the gzip-compressed snapshot of `language`, rebuilding it when a
+/// quote in that language changed or left it since the cached copy was made.
+/// Redis failures only cost a rebuild.
+pub async fn get_bundle(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    language: &str,
+) -> Result<(BundleMeta, Vec<u8>), AppError> {
+    // Read the cursor first: a change racing the build then shows up again in
+    // the feed instead of being skipped.
+    let cursor = pg::changes::fetch_latest_cursor(db, Some(language)).await?;
+
+    match rds::fetch_bundle(redis_client, language).await {
+        Ok(Some((meta, body))) if meta.cursor == cursor => return Ok((meta, body)),
+        Ok(_) => {}
+        Err(e) => error!("Failed to read cached bundle for '{}': {}", language, e),
+    }
+
+    let (meta, body) = build_bundle(db, language, cursor).await?;
+    info!(
+        "Built bundle for '{}' at cursor {} ({} quotes, {} bytes)",
+        language, cursor, meta.count, meta.size
+    );
+
+    if let Err(e) = rds::store_bundle(redis_client, &meta, &body).await {
+        error!("Failed to cache bundle for '{}': {}", language, e);
+    }
+    Ok((meta, body))
+}
@@ -0,0 +1,325 @@
+use crate::db::queries::pg;
+use crate::entities::categories;
+use crate::helper::languages;
+use crate::models::data::{
+    BulkCategoryRequest, BulkCategoryResponse, CategoryMergeRequest, CategoryMergeResponse,
+    CategoryRequest, CategoryUpdateRequest, PageQuery, PagedResponse, ResponseCategory,
+    ResponseQuote,
+};
+use crate::models::errors::AppError;
+
+use sea_orm::ActiveValue::Set;
+use sea_orm::{DatabaseConnection, IntoActiveModel};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Ids of `root` and every category below it.
+pub fn descendant_ids(all: &[categories::Model], root: i32) -> Vec<i32> {
+    let mut ids = vec![root];
+    let mut index = 0;
+
+    while index < ids.len() {
+        let parent = ids[index];
+        for category in all {
+            if category.parent_id == Some(parent) && !ids.contains(&category.id) {
+                ids.push(category.id);
+            }
+        }
+        index += 1;
+    }
+
+    ids
+}
+
+fn category_path(
+    all: &HashMap<i32, &categories::Model>,
+    category: &categories::Model,
+    label: &dyn Fn(&categories::Model) -> String,
+) -> Vec<String> {
+    let mut path = vec![label(category)];
+    let mut seen = HashSet::from([category.id]);
+    let mut current = category.parent_id;
+
+    while let Some(parent) = current.and_then(|id| all.get(&id)) {
+        if !seen.insert(parent.id) {
+            break;
+        }
+        path.push(label(parent));
+        current = parent.parent_id;
+    }
+
+    path.reverse();
+    path
+}
+
+/// Lists categories with their localised names, ancestry path and per-language
+/// counts of distinct quotes in the category or any of its descendants.
+pub async fn list_categories(
+    db: &DatabaseConnection,
+    language: Option<&str>,
+) -> Result<Vec<ResponseCategory>, AppError> {
+    let all = pg::categories::fetch_categories(db).await?;
+    let names = pg::categories::fetch_category_names(db).await?;
+    let links = pg::categories::fetch_category_links(db).await?;
+
+    let mut direct: HashMap<i32, Vec<(i32, &str)>> = HashMap::new();
+    for (category_id, quote_id, quote_language) in &links {
+        direct
+            .entry(*category_id)
+            .or_default()
+            .push((*quote_id, quote_language.as_str()));
+    }
+
+    let label = |category: &categories::Model| -> String {
+        language
+            .and_then(|lang| names.get(&category.id).and_then(|names| names.get(lang)))
+            .cloned()
+            .unwrap_or_else(|| category.name.clone())
+    };
+    let by_id: HashMap<i32, &categories::Model> =
+        all.iter().map(|category| (category.id, category)).collect();
+
+    Ok(all
+        .iter()
+        .map(|category| {
+            let mut quotes: HashSet<(i32, &str)> = HashSet::new();
+            for id in descendant_ids(&all, category.id) {
+                if let Some(links) = direct.get(&id) {
+                    quotes.extend(links.iter().copied());
+                }
+            }
+
+            let mut quote_counts: BTreeMap<String, u64> = BTreeMap::new();
+            for (_, quote_language) in quotes {
+                *quote_counts.entry(quote_language.to_string()).or_default() += 1;
+            }
+
+            ResponseCategory {
+                id: category.id,
+                slug: category.slug.clone(),
+                name: label(category),
+                names: names.get(&category.id).cloned().unwrap_or_default(),
+                parent_id: category.parent_id,
+                path: category_path(&by_id, category, &label),
+                quote_counts,
+            }
+        })
+        .collect())
+}
+
+pub async fn fetch_category_quotes(
+    db: &DatabaseConnection,
+    slug: &str,
+    language: Option<&str>,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    let category = pg::categories::fetch_category_by_slug(db, slug).await?;
+    let all = pg::categories::fetch_categories(db).await?;
+
+    pg::categories::fetch_quotes_in_categories(
+        db,
+        descendant_ids(&all, category.id),
+        language,
+        page,
+    )
+    .await
+}
+
+/// Lowercase ASCII slug with runs of anything else collapsed to `-`.
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for c in value.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+fn category_name(value: &str) -> Result<String, AppError> {
+    let name = value.trim();
+    match name.is_empty() {
+        true => Err(AppError::BadRequest(
+            "Category name must not be empty".to_string(),
+        )),
+        false => Ok(name.to_string()),
+    }
+}
+
+fn category_slug(requested: Option<&str>, name: &str) -> Result<String, AppError> {
+    let slug = slugify(requested.unwrap_or(name));
+    match slug.is_empty() {
+        true => Err(AppError::BadRequest(format!(
+            "Cannot derive a slug from '{}', provide one explicitly",
+            requested.unwrap_or(name)
+        ))),
+        false => Ok(slug),
+    }
+}
+
+async fn ensure_unique(
+    db: &DatabaseConnection,
+    name: Option<&str>,
+    slug: Option<&str>,
+    except_id: Option<i32>,
+) -> Result<(), AppError> {
+    match pg::categories::find_conflicting_category(db, name, slug, except_id).await? {
+        Some(existing) => Err(AppError::BadRequest(format!(
+            "Category {} already uses that name or slug ('{}', '{}')",
+            existing.id, existing.name, existing.slug
+        ))),
+        None => Ok(()),
+    }
+}
+
+async fn validate_names(
+    db: &DatabaseConnection,
+    names: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, AppError> {
+    let mut validated = BTreeMap::new();
+    for (language, name) in names {
+        validated.insert(
+            languages::validate_language(db, language).await?,
+            category_name(name)?,
+        );
+    }
+    Ok(validated)
+}
+
+pub async fn fetch_category(
+    db: &DatabaseConnection,
+    category_id: i32,
+) -> Result<ResponseCategory, AppError> {
+    list_categories(db, None)
+        .await?
+        .into_iter()
+        .find(|category| category.id == category_id)
+        .ok_or_else(|| AppError::NotFound(format!("Category {} not found", category_id)))
+}
+
+pub async fn create_category(
+    db: &DatabaseConnection,
+    request: &CategoryRequest,
+) -> Result<ResponseCategory, AppError> {
+    let name = category_name(&request.name)?;
+    let slug = category_slug(request.slug.as_deref(), &name)?;
+    let names = validate_names(db, &request.names).await?;
+
+    ensure_unique(db, Some(&name), Some(&slug), None).await?;
+    if let Some(parent_id) = request.parent_id {
+        pg::categories::fetch_category_by_id(db, parent_id).await?;
+    }
+
+    let category = pg::categories::insert_category(
+        db,
+        categories::ActiveModel {
+            name: Set(name),
+            slug: Set(slug),
+            parent_id: Set(request.parent_id),
+            ..Default::default()
+        },
+        &names,
+    )
+    .await?;
+
+    fetch_category(db, category.id).await
+}
+
+pub async fn update_category(
+    db: &DatabaseConnection,
+    category_id: i32,
+    request: &CategoryUpdateRequest,
+) -> Result<ResponseCategory, AppError> {
+    let mut category = pg::categories::fetch_category_by_id(db, category_id)
+        .await?
+        .into_active_model();
+
+    let name = request.name.as_deref().map(category_name).transpose()?;
+    let slug = request
+        .slug
+        .as_deref()
+        .map(|slug| category_slug(Some(slug), slug))
+        .transpose()?;
+    let names = match &request.names {
+        Some(names) => validate_names(db, names).await?,
+        None => BTreeMap::new(),
+    };
+
+    ensure_unique(db, name.as_deref(), slug.as_deref(), Some(category_id)).await?;
+    if let Some(name) = name {
+        category.name = Set(name);
+    }
+    if let Some(slug) = slug {
+        category.slug = Set(slug);
+    }
+
+    pg::categories::update_category(db, category, &names).await?;
+    fetch_category(db, category_id).await
+}
+
+pub async fn merge_category(
+    db: &DatabaseConnection,
+    source_id: i32,
+    request: &CategoryMergeRequest,
+    changed_by: Option<&str>,
+) -> Result<CategoryMergeResponse, AppError> {
+    if source_id == request.into {
+        return Err(AppError::BadRequest(
+            "A category cannot be merged into itself".to_string(),
+        ));
+    }
+
+    pg::categories::fetch_category_by_id(db, source_id).await?;
+    let target = pg::categories::fetch_category_by_id(db, request.into).await?;
+
+    // Re-parenting the source's children onto a grandchild would close a loop.
+    let all = pg::categories::fetch_categories(db).await?;
+    if target.parent_id != Some(source_id) && descendant_ids(&all, source_id).contains(&target.id) {
+        return Err(AppError::BadRequest(format!(
+            "Category {} is nested below category {} and cannot absorb it",
+            target.id, source_id
+        )));
+    }
+
+    let quotes_moved =
+        pg::categories::merge_categories(db, source_id, request.into, changed_by).await?;
+
+    Ok(CategoryMergeResponse {
+        merged_id: source_id,
+        into: fetch_category(db, request.into).await?,
+        quotes_moved,
+    })
+}
+
+pub async fn bulk_assign_categories(
+    db: &DatabaseConnection,
+    request: &BulkCategoryRequest,
+    changed_by: Option<&str>,
+) -> Result<BulkCategoryResponse, AppError> {
+    if request.quote_ids.is_empty() {
+        return Err(AppError::BadRequest(
+            "quote_ids must not be empty".to_string(),
+        ));
+    }
+    if let Some(id) = request.attach.iter().find(|id| request.detach.contains(id)) {
+        return Err(AppError::BadRequest(format!(
+            "Category {} cannot be attached and detached at once",
+            id
+        )));
+    }
+
+    pg::admin_quotes::ensure_quotes_exist(db, &request.quote_ids).await?;
+    pg::admin_quotes::ensure_categories_exist(db, &request.attach).await?;
+
+    let (attached, detached) = pg::categories::bulk_assign_categories(
+        db,
+        &request.quote_ids,
+        &request.attach,
+        &request.detach,
+        changed_by,
+    )
+    .await?;
+
+    Ok(BulkCategoryResponse { attached, detached })
+}
@@ -0,0 +1,72 @@
+use crate::db::queries::pg;
+use crate::models::data::{ChangeOp, ChangesResponse, QuoteChange};
+use crate::models::errors::AppError;
+use crate::utils::constants::ChangeFeed;
+
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+
+/// Returns the changes after cursor `since` (0 for everything) and the cursor
+/// to pass next time.
+pub async fn fetch_changes(
+    db: &DatabaseConnection,
+    since: i64,
+    language: Option<&str>,
+    limit: Option<u64>,
+) -> Result<ChangesResponse, AppError> {
+    let limit = limit
+        .unwrap_or(ChangeFeed::DefaultLimit.as_u64())
+        .clamp(1, ChangeFeed::MaxLimit.as_u64());
+
+    let mut rows = pg::changes::fetch_changes(db, since, language, limit + 1).await?;
+    let has_more = rows.len() as u64 > limit;
+    rows.truncate(limit as usize);
+
+    let cursor = rows.last().map(|(entry, _)| entry.seq).unwrap_or(since);
+    let live: Vec<_> = rows
+        .iter()
+        .filter(|(entry, _)| !entry.deleted)
+        .filter_map(|(_, quote)| quote.clone())
+        .filter(|quote| quote.deleted_at.is_none())
+        .collect();
+    let mut quotes: HashMap<i32, _> = pg::to_response_quotes(db, live)
+        .await?
+        .into_iter()
+        .map(|quote| (quote.id, quote))
+        .collect();
+
+    let changes = rows
+        .into_iter()
+        .map(|(entry, quote)| {
+            // A deletion in one language may be a move to another, so the
+            // payload is only sent for live entries.
+            let quote = if entry.deleted {
+                None
+            } else {
+                quote.and_then(|quote| quotes.remove(&quote.id).map(|response| (quote, response)))
+            };
+            let (op, payload) = match quote {
+                None => (ChangeOp::Delete, None),
+                Some((row, response)) if row.created_at == row.updated_at => {
+                    (ChangeOp::Insert, Some(response))
+                }
+                Some((_, response)) => (ChangeOp::Update, Some(response)),
+            };
+
+            QuoteChange {
+                cursor: entry.seq,
+                op,
+                quote_id: entry.quote_id,
+                language: entry.language,
+                changed_at: entry.changed_at.into(),
+                quote: payload,
+            }
+        })
+        .collect();
+
+    Ok(ChangesResponse {
+        changes,
+        cursor,
+        has_more,
+    })
+}
@@ -0,0 +1,518 @@
+use crate::helper::transliteration;
+use crate::models::data::{ResponseQuote, ResponseReference};
+use crate::models::errors::AppError;
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationStyle {
+    Apa,
+    Mla,
+    Chicago,
+    Bibtex,
+    Ris,
+}
+
+impl CitationStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CitationStyle::Apa => "apa",
+            CitationStyle::Mla => "mla",
+            CitationStyle::Chicago => "chicago",
+            CitationStyle::Bibtex => "bibtex",
+            CitationStyle::Ris => "ris",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            CitationStyle::Apa | CitationStyle::Mla | CitationStyle::Chicago => {
+                "text/plain; charset=utf-8"
+            }
+            CitationStyle::Bibtex => "application/x-bibtex; charset=utf-8",
+            CitationStyle::Ris => "application/x-research-info-systems; charset=utf-8",
+        }
+    }
+
+    pub fn from_accept(accept: &str) -> Option<CitationStyle> {
+        if accept.contains("application/x-bibtex") {
+            Some(CitationStyle::Bibtex)
+        } else if accept.contains("application/x-research-info-systems") {
+            Some(CitationStyle::Ris)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for CitationStyle {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "apa" => Ok(CitationStyle::Apa),
+            "mla" => Ok(CitationStyle::Mla),
+            "chicago" => Ok(CitationStyle::Chicago),
+            "bibtex" => Ok(CitationStyle::Bibtex),
+            "ris" => Ok(CitationStyle::Ris),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported citation style '{}', expected one of apa, mla, chicago, bibtex, ris",
+                other
+            ))),
+        }
+    }
+}
+
+pub fn render_citation(quote: &ResponseQuote, style: CitationStyle) -> String {
+    let reference = primary_reference(quote);
+
+    match style {
+        CitationStyle::Apa => render_apa(quote, &reference),
+        CitationStyle::Mla => render_mla(quote, &reference),
+        CitationStyle::Chicago => render_chicago(quote, &reference),
+        CitationStyle::Bibtex => render_bibtex(quote, &reference),
+        CitationStyle::Ris => render_ris(quote, &reference),
+    }
+}
+
+/// Quotes without structured sources fall back to the legacy reference string
+/// as the title of the cited work.
+fn primary_reference(quote: &ResponseQuote) -> ResponseReference {
+    quote
+        .references
+        .first()
+        .cloned()
+        .unwrap_or_else(|| ResponseReference {
+            kind: "other".to_string(),
+            title: quote.reference.clone(),
+            ..Default::default()
+        })
+}
+
+fn render_apa(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let year = reference
+        .year
+        .map(|year| year.to_string())
+        .unwrap_or_else(|| "n.d.".to_string());
+
+    let mut details = Vec::new();
+    if let Some(edition) = &reference.edition {
+        details.push(format!("{} ed.", edition));
+    }
+    if let Some(volume) = &reference.volume {
+        details.push(format!("Vol. {}", volume));
+    }
+    if let Some(number) = &reference.number {
+        details.push(format!("No. {}", number));
+    }
+    if let Some(page) = &reference.page {
+        details.push(format!("p. {}", page));
+    }
+
+    let mut citation = format!("{}. ({}). {}", quote.author, year, reference.title);
+    if !details.is_empty() {
+        let _ = write!(citation, " ({})", details.join(", "));
+    }
+    citation.push('.');
+    if let Some(publisher) = &reference.publisher {
+        let _ = write!(citation, " {}.", publisher);
+    }
+    if let Some(url) = &reference.url {
+        let _ = write!(citation, " {}", url);
+    }
+    citation
+}
+
+fn render_mla(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let mut parts = vec![format!("{}. {}.", quote.author, reference.title)];
+
+    let mut container = Vec::new();
+    if let Some(collection) = &reference.collection {
+        container.push(collection.clone());
+    }
+    if let Some(edition) = &reference.edition {
+        container.push(format!("{} ed.", edition));
+    }
+    if let Some(volume) = &reference.volume {
+        container.push(format!("vol. {}", volume));
+    }
+    if let Some(number) = &reference.number {
+        container.push(format!("no. {}", number));
+    }
+    if let Some(publisher) = &reference.publisher {
+        container.push(publisher.clone());
+    }
+    if let Some(year) = reference.year {
+        container.push(year.to_string());
+    }
+    if let Some(page) = &reference.page {
+        container.push(format!("p. {}", page));
+    }
+    if let Some(url) = &reference.url {
+        container.push(url.clone());
+    }
+    if !container.is_empty() {
+        parts.push(format!("{}.", container.join(", ")));
+    }
+
+    parts.join(" ")
+}
+
+fn render_chicago(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let mut citation = format!("{}. {}.", quote.author, reference.title);
+
+    if let Some(edition) = &reference.edition {
+        let _ = write!(citation, " {} ed.", edition);
+    }
+    if let Some(volume) = &reference.volume {
+        let _ = write!(citation, " Vol. {}.", volume);
+    }
+
+    let imprint = match (&reference.publisher, reference.year) {
+        (Some(publisher), Some(year)) => Some(format!("{}, {}", publisher, year)),
+        (Some(publisher), None) => Some(publisher.clone()),
+        (None, Some(year)) => Some(year.to_string()),
+        (None, None) => None,
+    };
+    if let Some(imprint) = imprint {
+        let _ = write!(citation, " {}.", imprint);
+    }
+    if let Some(number) = &reference.number {
+        let _ = write!(citation, " No. {}.", number);
+    }
+    if let Some(page) = &reference.page {
+        let _ = write!(citation, " p. {}.", page);
+    }
+    if let Some(url) = &reference.url {
+        let _ = write!(citation, " {}.", url);
+    }
+    citation
+}
+
+fn bibtex_entry_type(reference: &ResponseReference) -> &'static str {
+    match reference.kind.as_str() {
+        "book" | "hadith" => "book",
+        _ => "misc",
+    }
+}
+
+/// BibTeX keys must be plain ASCII, so Arabic-script author names are
+/// transliterated and anything left over is dropped. Authors that leave
+/// nothing behind fall back to `quote{id}`.
+fn citation_key(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let author: String =
+        transliteration::urdu_to_roman(quote.author.split_whitespace().last().unwrap_or_default())
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+    if author.is_empty() {
+        return format!("quote{}", quote.id);
+    }
+
+    let year = reference
+        .year
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+
+    format!("{}{}q{}", author, year, quote.id)
+}
+
+/// Indices of the braces in `value` that have no partner. BibTeX counts
+/// braces even when they are backslash-escaped, so these are the only ones
+/// that need replacing.
+fn unbalanced_braces(value: &str) -> Vec<usize> {
+    let mut open = Vec::new();
+    let mut unbalanced = Vec::new();
+    for (index, c) in value.char_indices() {
+        match c {
+            '{' => open.push(index),
+            '}' if open.pop().is_none() => unbalanced.push(index),
+            _ => {}
+        }
+    }
+    unbalanced.extend(open);
+    unbalanced
+}
+
+/// Escapes a value for a brace-delimited field. Balanced braces are kept as
+/// grouping, unbalanced ones and the LaTeX specials are spelled out.
+fn escape_bibtex(value: &str) -> String {
+    let unbalanced = unbalanced_braces(value);
+    let mut escaped = String::with_capacity(value.len());
+    for (index, c) in value.char_indices() {
+        match c {
+            '{' if unbalanced.contains(&index) => escaped.push_str("\\textbraceleft{}"),
+            '}' if unbalanced.contains(&index) => escaped.push_str("\\textbraceright{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '%' | '&' | '#' | '_' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+/// URLs are read verbatim, so only the field delimiters need protecting.
+fn escape_bibtex_url(value: &str) -> String {
+    let unbalanced = unbalanced_braces(value);
+    value
+        .char_indices()
+        .map(|(index, c)| match c {
+            '{' if unbalanced.contains(&index) => "%7B".to_string(),
+            '}' if unbalanced.contains(&index) => "%7D".to_string(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+fn render_bibtex(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let mut fields = vec![
+        ("author", quote.author.clone()),
+        ("title", reference.title.clone()),
+    ];
+    if let Some(year) = reference.year {
+        fields.push(("year", year.to_string()));
+    }
+    if let Some(publisher) = &reference.publisher {
+        fields.push(("publisher", publisher.clone()));
+    }
+    if let Some(edition) = &reference.edition {
+        fields.push(("edition", edition.clone()));
+    }
+    if let Some(collection) = &reference.collection {
+        fields.push(("series", collection.clone()));
+    }
+    if let Some(volume) = &reference.volume {
+        fields.push(("volume", volume.clone()));
+    }
+    if let Some(chapter) = &reference.chapter {
+        fields.push(("chapter", chapter.clone()));
+    }
+    if let Some(number) = &reference.number {
+        fields.push(("number", number.clone()));
+    }
+    if let Some(page) = &reference.page {
+        fields.push(("pages", page.clone()));
+    }
+    if let Some(url) = &reference.url {
+        fields.push(("url", url.clone()));
+    }
+    fields.push(("language", quote.language.clone()));
+    fields.push(("note", format!("Quoted: {}", quote.content)));
+
+    let body = fields
+        .iter()
+        .map(|(name, value)| {
+            let value = match *name {
+                "url" => escape_bibtex_url(value),
+                _ => escape_bibtex(value),
+            };
+            format!("  {} = {{{}}}", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!(
+        "@{}{{{},\n{}\n}}\n",
+        bibtex_entry_type(reference),
+        citation_key(quote, reference),
+        body
+    )
+}
+
+/// RIS is line-oriented, so embedded newlines would start a bogus tag.
+fn ris_value(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn render_ris(quote: &ResponseQuote, reference: &ResponseReference) -> String {
+    let entry_type = match reference.kind.as_str() {
+        "book" | "hadith" => "BOOK",
+        "web" => "ELEC",
+        _ => "GEN",
+    };
+
+    let mut lines = vec![
+        format!("TY  - {}", entry_type),
+        format!("AU  - {}", ris_value(&quote.author)),
+        format!("TI  - {}", ris_value(&reference.title)),
+    ];
+    if let Some(collection) = &reference.collection {
+        lines.push(format!("T2  - {}", ris_value(collection)));
+    }
+    if let Some(year) = reference.year {
+        lines.push(format!("PY  - {}", year));
+    }
+    if let Some(publisher) = &reference.publisher {
+        lines.push(format!("PB  - {}", ris_value(publisher)));
+    }
+    if let Some(edition) = &reference.edition {
+        lines.push(format!("ET  - {}", ris_value(edition)));
+    }
+    if let Some(volume) = &reference.volume {
+        lines.push(format!("VL  - {}", ris_value(volume)));
+    }
+    if let Some(number) = &reference.number {
+        lines.push(format!("IS  - {}", ris_value(number)));
+    }
+    if let Some(page) = &reference.page {
+        lines.push(format!("SP  - {}", ris_value(page)));
+    }
+    if let Some(url) = &reference.url {
+        lines.push(format!("UR  - {}", ris_value(url)));
+    }
+    lines.push(format!("LA  - {}", ris_value(&quote.language)));
+    lines.push(format!("N1  - {}", ris_value(&quote.content)));
+    lines.push("ER  - ".to_string());
+
+    lines.join("\r\n") + "\r\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(author: &str, content: &str) -> ResponseQuote {
+        ResponseQuote {
+            id: 7,
+            content: content.to_string(),
+            author: author.to_string(),
+            reference: "Ihya".to_string(),
+            language: "en".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn book() -> ResponseReference {
+        ResponseReference {
+            kind: "book".to_string(),
+            title: "Ihya Ulum al-Din".to_string(),
+            volume: Some("3".to_string()),
+            page: Some("112".to_string()),
+            publisher: Some("Dar al-Minhaj".to_string()),
+            year: Some(2011),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn escape_bibtex_escapes_latex_specials() {
+        assert_eq!(
+            escape_bibtex("100% & #1 a_b $5"),
+            "100\\% \\& \\#1 a\\_b \\$5"
+        );
+        assert_eq!(escape_bibtex("a\\b"), "a\\textbackslash{}b");
+        assert_eq!(escape_bibtex("~^"), "\\textasciitilde{}\\textasciicircum{}");
+    }
+
+    #[test]
+    fn escape_bibtex_keeps_balanced_braces() {
+        assert_eq!(escape_bibtex("The {Qur'an}"), "The {Qur'an}");
+        assert_eq!(escape_bibtex("{a{b}c}"), "{a{b}c}");
+    }
+
+    #[test]
+    fn escape_bibtex_spells_out_unbalanced_braces() {
+        assert_eq!(
+            escape_bibtex("a}b{c"),
+            "a\\textbraceright{}b\\textbraceleft{}c"
+        );
+        assert_eq!(escape_bibtex("{{x}"), "\\textbraceleft{}{x}");
+    }
+
+    #[test]
+    fn bibtex_url_is_not_latex_escaped() {
+        assert_eq!(
+            escape_bibtex_url("https://example.org/a_b?x=1%20&y={"),
+            "https://example.org/a_b?x=1%20&y=%7B"
+        );
+    }
+
+    #[test]
+    fn citation_key_is_ascii_for_latin_authors() {
+        assert_eq!(
+            citation_key(&quote("Imam Al-Ghazali", ""), &book()),
+            "alghazali2011q7"
+        );
+    }
+
+    #[test]
+    fn citation_key_transliterates_arabic_authors() {
+        let key = citation_key(&quote("أبو حامد الغزالي", ""), &book());
+        assert!(key.is_ascii(), "{}", key);
+        assert!(key.ends_with("2011q7"), "{}", key);
+        assert!(key.len() > "2011q7".len(), "{}", key);
+    }
+
+    #[test]
+    fn citation_key_falls_back_to_quote_id() {
+        assert_eq!(citation_key(&quote("", ""), &book()), "quote7");
+        assert_eq!(citation_key(&quote("孔子", ""), &book()), "quote7");
+    }
+
+    #[test]
+    fn bibtex_entry_escapes_fields() {
+        let rendered = render_bibtex(&quote("Rumi", "50% of {it"), &book());
+        assert!(rendered.starts_with("@book{rumi2011q7,\n"), "{}", rendered);
+        assert!(
+            rendered.contains("note = {Quoted: 50\\% of \\textbraceleft{}it}"),
+            "{}",
+            rendered
+        );
+        assert!(rendered.ends_with("\n}\n"));
+    }
+
+    #[test]
+    fn ris_collapses_embedded_newlines() {
+        let mut reference = book();
+        reference.title = "Line one\nline two".to_string();
+        let rendered = render_ris(&quote("Rumi", "First\r\nsecond"), &reference);
+
+        assert!(rendered.contains("TI  - Line one line two\r\n"));
+        assert!(rendered.contains("N1  - First second\r\n"));
+        assert!(rendered
+            .split("\r\n")
+            .filter(|line| !line.is_empty())
+            .all(|line| line.len() >= 5 && &line[2..5] == "  -"));
+        assert!(rendered.ends_with("ER  - \r\n"));
+    }
+
+    #[test]
+    fn chicago_prefixes_the_page() {
+        assert_eq!(
+            render_chicago(&quote("Rumi", ""), &book()),
+            "Rumi. Ihya Ulum al-Din. Vol. 3. Dar al-Minhaj, 2011. p. 112."
+        );
+    }
+
+    #[test]
+    fn apa_without_year_uses_nd() {
+        let mut reference = book();
+        reference.year = None;
+        assert_eq!(
+            render_apa(&quote("Rumi", ""), &reference),
+            "Rumi. (n.d.). Ihya Ulum al-Din (Vol. 3, p. 112). Dar al-Minhaj."
+        );
+    }
+
+    #[test]
+    fn mla_lists_the_container() {
+        assert_eq!(
+            render_mla(&quote("Rumi", ""), &book()),
+            "Rumi. Ihya Ulum al-Din. vol. 3, Dar al-Minhaj, 2011, p. 112."
+        );
+    }
+
+    #[test]
+    fn legacy_reference_becomes_the_title() {
+        let reference = primary_reference(&quote("Rumi", ""));
+        assert_eq!(reference.title, "Ihya");
+        assert_eq!(reference.kind, "other");
+    }
+}
@@ -0,0 +1,586 @@
+use crate::db::queries::pg;
+use crate::helper::{arabic, languages};
+use crate::models::data::{DuplicatePair, DuplicateReport};
+use crate::models::errors::AppError;
+use crate::utils;
+
+use log::error;
+use sea_orm::DatabaseConnection;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Mutex, MutexGuard};
+
+/// Characters per shingle.
+const SHINGLE_SIZE: usize = 4;
+/// MinHash signature length, split into `BANDS` bands for LSH bucketing.
+const SIGNATURE_SIZE: usize = 64;
+const BANDS: usize = 16;
+const ROWS_PER_BAND: usize = SIGNATURE_SIZE / BANDS;
+
+const DEFAULT_THRESHOLD: f64 = 0.85;
+/// With 16 bands of 4 rows, a pair becomes a candidate with probability
+/// 1 - (1 - s^4)^16: 0.99 at a similarity of 0.7 but only 0.64 at 0.5.
+/// Lower thresholds would silently miss most of the pairs they ask for.
+const MIN_THRESHOLD: f64 = 0.7;
+/// Changes applied to a cached index before it is rebuilt instead.
+const MAX_CATCH_UP: u64 = 500;
+
+/// What admin writes do with a near-duplicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupMode {
+    Warn,
+    Reject,
+}
+
+/// `DEDUP_MODE=reject` refuses near-duplicates on admin create and import;
+/// the default only warns.
+pub fn dedup_mode() -> DedupMode {
+    match utils::env::load_env_var("DEDUP_MODE").ok().as_deref() {
+        Some("reject") => DedupMode::Reject,
+        _ => DedupMode::Warn,
+    }
+}
+
+/// Similarity from which two quotes count as duplicates, configurable through
+/// `DEDUP_THRESHOLD`.
+pub fn dedup_threshold() -> f64 {
+    let Ok(value) = utils::env::load_env_var("DEDUP_THRESHOLD") else {
+        return DEFAULT_THRESHOLD;
+    };
+    match value
+        .parse()
+        .map_err(|_| value.clone())
+        .and_then(check_threshold)
+    {
+        Ok(threshold) => threshold,
+        Err(e) => {
+            error!("Ignoring DEDUP_THRESHOLD: {}", e);
+            DEFAULT_THRESHOLD
+        }
+    }
+}
+
+fn check_threshold(threshold: f64) -> Result<f64, String> {
+    if (MIN_THRESHOLD..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err(format!(
+            "Threshold {} must be between {} and 1",
+            threshold, MIN_THRESHOLD
+        ))
+    }
+}
+
+pub fn parse_threshold(value: Option<f64>) -> Result<f64, AppError> {
+    match value {
+        None => Ok(dedup_threshold()),
+        Some(threshold) => check_threshold(threshold).map_err(AppError::BadRequest),
+    }
+}
+
+/// Search normalisation plus punctuation removal, so quotes that differ only
+/// in case, diacritics or punctuation compare equal.
+pub fn normalize(text: &str) -> String {
+    arabic::normalize(text)
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shingles(normalized: &str) -> HashSet<u64> {
+    let chars: Vec<char> = normalized.chars().collect();
+    if chars.len() <= SHINGLE_SIZE {
+        return HashSet::from([hash64(0, normalized)]);
+    }
+
+    chars
+        .windows(SHINGLE_SIZE)
+        .map(|window| hash64(0, &window.iter().collect::<String>()))
+        .collect()
+}
+
+/// FNV-1a, seeded so one hash family yields every MinHash permutation and
+/// signatures stay stable across runs.
+fn hash64(seed: u64, value: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    for byte in value.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn mix(seed: u64, value: u64) -> u64 {
+    let mut x = value ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^ (x >> 33)
+}
+
+fn signature(shingles: &HashSet<u64>) -> [u64; SIGNATURE_SIZE] {
+    let mut signature = [u64::MAX; SIGNATURE_SIZE];
+    for shingle in shingles {
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            *slot = (*slot).min(mix(seed as u64 + 1, *shingle));
+        }
+    }
+    signature
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    match union {
+        0 => 0.0,
+        _ => a.intersection(b).count() as f64 / union as f64,
+    }
+}
+
+struct Entry<K> {
+    key: K,
+    language: String,
+    shingles: HashSet<u64>,
+    bands: Vec<(String, usize, u64)>,
+}
+
+/// MinHash/LSH index of quotes per language. Candidates sharing a band are
+/// confirmed with the exact Jaccard similarity of their shingles.
+pub struct DedupIndex<K> {
+    entries: Vec<Option<Entry<K>>>,
+    buckets: HashMap<(String, usize, u64), Vec<usize>>,
+    positions: HashMap<K, Vec<usize>>,
+    removed: usize,
+}
+
+impl<K: Clone + Eq + Hash> DedupIndex<K> {
+    pub fn new() -> Self {
+        DedupIndex {
+            entries: Vec::new(),
+            buckets: HashMap::new(),
+            positions: HashMap::new(),
+            removed: 0,
+        }
+    }
+
+    fn band_keys(language: &str, signature: &[u64; SIGNATURE_SIZE]) -> Vec<(String, usize, u64)> {
+        signature
+            .chunks(ROWS_PER_BAND)
+            .enumerate()
+            .map(|(band, rows)| {
+                let band_hash = rows.iter().fold(band as u64, |hash, row| mix(hash, *row));
+                (language.to_string(), band, band_hash)
+            })
+            .collect()
+    }
+
+    fn push(&mut self, entry: Entry<K>) {
+        let index = self.entries.len();
+        for band_key in &entry.bands {
+            self.buckets
+                .entry(band_key.clone())
+                .or_default()
+                .push(index);
+        }
+        self.positions
+            .entry(entry.key.clone())
+            .or_default()
+            .push(index);
+        self.entries.push(Some(entry));
+    }
+
+    pub fn insert(&mut self, key: K, language: &str, text: &str) {
+        let shingles = shingles(&normalize(text));
+        let bands = Self::band_keys(language, &signature(&shingles));
+        self.push(Entry {
+            key,
+            language: language.to_string(),
+            shingles,
+            bands,
+        });
+    }
+
+    /// Drops every entry for `key` from the index and its buckets. The freed
+    /// slots are reclaimed once they outnumber the live entries.
+    pub fn remove(&mut self, key: &K) {
+        for index in self.positions.remove(key).unwrap_or_default() {
+            let Some(entry) = self.entries[index].take() else {
+                continue;
+            };
+            for band_key in &entry.bands {
+                if let Some(bucket) = self.buckets.get_mut(band_key) {
+                    bucket.retain(|slot| *slot != index);
+                    if bucket.is_empty() {
+                        self.buckets.remove(band_key);
+                    }
+                }
+            }
+            self.removed += 1;
+        }
+
+        if self.removed > self.len() {
+            self.compact();
+        }
+    }
+
+    fn compact(&mut self) {
+        let entries = std::mem::take(&mut self.entries);
+        self.buckets.clear();
+        self.positions.clear();
+        self.removed = 0;
+        for entry in entries.into_iter().flatten() {
+            self.push(entry);
+        }
+    }
+
+    /// Number of live entries.
+    fn len(&self) -> usize {
+        self.entries.len() - self.removed
+    }
+
+    /// Indexed quotes in `language` at least `threshold` similar to `text`,
+    /// most similar first.
+    pub fn find_similar(&self, language: &str, text: &str, threshold: f64) -> Vec<(K, f64)> {
+        let shingles = shingles(&normalize(text));
+        let candidates: HashSet<usize> = Self::band_keys(language, &signature(&shingles))
+            .iter()
+            .filter_map(|band_key| self.buckets.get(band_key))
+            .flatten()
+            .copied()
+            .collect();
+
+        let mut matches: Vec<(K, f64)> = candidates
+            .into_iter()
+            .filter_map(|index| self.entries[index].as_ref())
+            .filter(|entry| entry.language == language)
+            .map(|entry| (entry.key.clone(), jaccard(&shingles, &entry.shingles)))
+            .filter(|(_, similarity)| *similarity >= threshold)
+            .collect();
+
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+        matches
+    }
+}
+
+impl<K: Clone + Eq + Hash> Default for DedupIndex<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders matches as `quote 12 (0.91), row 3 (0.88)` for error messages.
+pub fn describe_matches<K: std::fmt::Display>(matches: &[(K, f64)]) -> String {
+    matches
+        .iter()
+        .map(|(key, similarity)| format!("{} ({:.2})", key, similarity))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Index of one language's live quotes and the change cursor it reflects.
+struct CachedIndex {
+    cursor: i64,
+    index: DedupIndex<i32>,
+}
+
+/// Indexes built for admin creates, per language. The lock only covers
+/// in-memory work: reads from Postgres happen with it released, and their
+/// results are applied afterwards.
+static INDEX_CACHE: Mutex<BTreeMap<String, CachedIndex>> = Mutex::new(BTreeMap::new());
+
+fn lock_cache() -> MutexGuard<'static, BTreeMap<String, CachedIndex>> {
+    INDEX_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn build_index(db: &DatabaseConnection, language: &str) -> Result<CachedIndex, AppError> {
+    // Read the cursor first, so a change racing the build is applied again
+    // rather than missed.
+    let cursor = pg::changes::fetch_latest_cursor(db, Some(language)).await?;
+    let mut index = DedupIndex::new();
+    for (id, quote_language, quote) in
+        pg::import::fetch_contents(db, Some(&[language.to_string()])).await?
+    {
+        index.insert(id, &quote_language, &quote);
+    }
+    Ok(CachedIndex { cursor, index })
+}
+
+/// Brings the cached index for `language` up to date: changes since its
+/// cursor are applied, or the index is rebuilt when there is none yet or too
+/// many changes piled up.
+async fn refresh_index(db: &DatabaseConnection, language: &str) -> Result<(), AppError> {
+    let cursor = lock_cache().get(language).map(|cached| cached.cursor);
+
+    if let Some(cursor) = cursor {
+        let changes =
+            pg::changes::fetch_changes(db, cursor, Some(language), MAX_CATCH_UP + 1).await?;
+        if changes.len() as u64 <= MAX_CATCH_UP {
+            let mut cache = lock_cache();
+            if let Some(cached) = cache.get_mut(language) {
+                // Another request may have applied some of these already.
+                let applied = cached.cursor;
+                for (entry, quote) in changes.into_iter().filter(|(entry, _)| entry.seq > applied) {
+                    cached.index.remove(&entry.quote_id);
+                    if let Some(quote) =
+                        quote.filter(|quote| !entry.deleted && quote.deleted_at.is_none())
+                    {
+                        cached.index.insert(quote.id, &quote.language, &quote.quote);
+                    }
+                    cached.cursor = entry.seq;
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    let built = build_index(db, language).await?;
+    let mut cache = lock_cache();
+    if cache
+        .get(language)
+        .is_none_or(|cached| cached.cursor < built.cursor)
+    {
+        cache.insert(language.to_string(), built);
+    }
+    Ok(())
+}
+
+/// Checks a quote about to be created against the live quotes in its
+/// language. In reject mode a match is an error; otherwise the similar quote
+/// ids are returned so the caller can warn about them.
+pub async fn check_new_quote(
+    db: &DatabaseConnection,
+    language: &str,
+    content: &str,
+) -> Result<Vec<i32>, AppError> {
+    refresh_index(db, language).await?;
+    let matches = lock_cache()
+        .get(language)
+        .map(|cached| {
+            cached
+                .index
+                .find_similar(language, content, dedup_threshold())
+        })
+        .unwrap_or_default();
+    if matches.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match dedup_mode() {
+        DedupMode::Reject => Err(AppError::BadRequest(format!(
+            "Quote is a possible duplicate of quote {}",
+            describe_matches(&matches)
+        ))),
+        DedupMode::Warn => Ok(matches.into_iter().map(|(id, _)| id).collect()),
+    }
+}
+
+/// Pairs of live quotes in the same language that are at least `threshold`
+/// similar, most similar first.
+pub async fn duplicate_report(
+    db: &DatabaseConnection,
+    lang: Option<&str>,
+    threshold: Option<f64>,
+) -> Result<DuplicateReport, AppError> {
+    let threshold = parse_threshold(threshold)?;
+    let languages = match lang {
+        Some(lang) => Some(vec![languages::validate_language(db, lang).await?]),
+        None => None,
+    };
+
+    let quotes = pg::import::fetch_contents(db, languages.as_deref()).await?;
+    let mut index = DedupIndex::new();
+    let mut pairs = Vec::new();
+
+    for (position, (id, language, content)) in quotes.iter().enumerate() {
+        for (earlier, similarity) in index.find_similar(language, content, threshold) {
+            let (earlier_id, _, earlier_content): &(i32, String, String) = &quotes[earlier];
+            pairs.push(DuplicatePair {
+                language: language.clone(),
+                similarity,
+                quote_ids: [*earlier_id, *id],
+                contents: [earlier_content.clone(), content.clone()],
+            });
+        }
+        index.insert(position, language, content);
+    }
+
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(a.quote_ids.cmp(&b.quote_ids))
+    });
+
+    Ok(DuplicateReport { threshold, pairs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic word soup, so the recall figures are stable.
+    struct Words(u64);
+
+    impl Words {
+        fn next(&mut self) -> String {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let mut n = (self.0 >> 33) % 5000;
+            let mut word = String::new();
+            for _ in 0..4 {
+                word.push((b'a' + (n % 26) as u8) as char);
+                n /= 26;
+            }
+            word
+        }
+
+        fn sentence(&mut self, len: usize) -> Vec<String> {
+            (0..len).map(|_| self.next()).collect()
+        }
+    }
+
+    fn candidate_probability(similarity: f64) -> f64 {
+        1.0 - (1.0 - similarity.powi(ROWS_PER_BAND as i32)).powi(BANDS as i32)
+    }
+
+    fn similarity(a: &str, b: &str) -> f64 {
+        jaccard(&shingles(&normalize(a)), &shingles(&normalize(b)))
+    }
+
+    /// Pairs of quotes with `edits` of their 30 words replaced, with their
+    /// exact similarity and whether the index found them.
+    fn recall_pairs(edits: usize) -> Vec<(f64, bool)> {
+        let mut words = Words(edits as u64 + 1);
+        let mut index = DedupIndex::new();
+        let mut pairs = Vec::new();
+
+        for id in 0..200 {
+            let original = words.sentence(30);
+            index.insert(id, "en", &original.join(" "));
+
+            let mut edited = original.clone();
+            for position in 0..edits {
+                edited[position * 30 / edits] = words.next();
+            }
+            pairs.push((id, original.join(" "), edited.join(" ")));
+        }
+
+        pairs
+            .into_iter()
+            .map(|(id, original, edited)| {
+                let found = index
+                    .find_similar("en", &edited, 0.0)
+                    .iter()
+                    .any(|(key, _)| *key == id);
+                (similarity(&original, &edited), found)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn minimum_threshold_keeps_candidate_recall_high() {
+        assert!(candidate_probability(MIN_THRESHOLD) > 0.98);
+        assert!(candidate_probability(DEFAULT_THRESHOLD) > 0.9999);
+        assert!(candidate_probability(0.5) < 0.7);
+    }
+
+    #[test]
+    fn finds_every_pair_above_the_default_threshold() {
+        let pairs = recall_pairs(1);
+        assert!(pairs.iter().all(|(s, _)| *s >= DEFAULT_THRESHOLD));
+        assert!(pairs.iter().all(|(_, found)| *found));
+    }
+
+    #[test]
+    fn finds_nearly_every_pair_above_the_minimum_threshold() {
+        let pairs: Vec<_> = recall_pairs(3)
+            .into_iter()
+            .filter(|(s, _)| *s >= MIN_THRESHOLD)
+            .collect();
+        assert!(pairs.len() > 100);
+
+        let found = pairs.iter().filter(|(_, found)| *found).count();
+        assert!(found as f64 / pairs.len() as f64 >= 0.95);
+    }
+
+    #[test]
+    fn unrelated_quotes_are_not_matched() {
+        let mut words = Words(42);
+        let mut index = DedupIndex::new();
+        for id in 0..200 {
+            index.insert(id, "en", &words.sentence(30).join(" "));
+        }
+        let probe = words.sentence(30).join(" ");
+        assert!(index.find_similar("en", &probe, MIN_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn matches_ignore_case_punctuation_and_other_languages() {
+        let mut index = DedupIndex::new();
+        index.insert(1, "en", "Seek knowledge from the cradle to the grave.");
+        index.insert(2, "fr", "Seek knowledge from the cradle to the grave.");
+
+        let matches = index.find_similar("en", "seek knowledge, from the cradle to the grave", 0.9);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, 1);
+        assert!((matches[0].1 - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn removed_entries_are_not_matched() {
+        let mut index = DedupIndex::new();
+        index.insert(1, "en", "Patience is the key to relief");
+        index.insert(2, "en", "Patience is the key to relief!");
+        index.remove(&1);
+
+        let matches = index.find_similar("en", "Patience is the key to relief", 0.9);
+        assert_eq!(
+            matches.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn repeated_edits_do_not_grow_the_index() {
+        let mut index = DedupIndex::new();
+        index.insert(1, "en", "Patience is the key to relief");
+        index.insert(2, "en", "Knowledge without action is madness");
+        let buckets = index.buckets.len();
+
+        for round in 0..100 {
+            index.remove(&1);
+            let text = if round % 2 == 0 {
+                "Patience is the key to relief"
+            } else {
+                "Patience is the key to relief!"
+            };
+            index.insert(1, "en", text);
+        }
+
+        assert_eq!(index.len(), 2);
+        assert!(index.entries.len() <= 2 * index.len() + 1);
+        assert!(index.buckets.len() <= 2 * buckets);
+        assert!(index.buckets.values().all(|bucket| bucket.len() <= 2));
+        assert_eq!(
+            index
+                .find_similar("en", "Patience is the key to relief", 0.9)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_threshold_rejects_values_below_the_floor() {
+        assert_eq!(parse_threshold(Some(0.9)).unwrap(), 0.9);
+        assert_eq!(parse_threshold(Some(MIN_THRESHOLD)).unwrap(), MIN_THRESHOLD);
+        assert!(parse_threshold(Some(0.5)).is_err());
+        assert!(parse_threshold(Some(1.5)).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use crate::db::queries::pg;
+use crate::helper::import::{CsvQuoteRecord, CSV_COLUMNS};
+use crate::models::data::{ExportHeader, QuoteRecord};
+use crate::models::errors::AppError;
+use crate::utils::constants::EXPORT_SCHEMA_VERSION;
+
+use actix_web::web::Bytes;
+use chrono::Utc;
+use futures_util::stream::{self, Stream};
+use sea_orm::{
+    AccessMode, DatabaseConnection, DatabaseTransaction, IsolationLevel, TransactionTrait,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Quotes fetched per database round trip while streaming an export.
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+            "csv" => Ok(ExportFormat::Csv),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported export format '{}', expected one of json, ndjson, csv",
+                other
+            ))),
+        }
+    }
+}
+
+enum Stage {
+    Header,
+    /// Every batch is read through `snapshot`, a repeatable-read transaction,
+    /// so the export is consistent even while quotes are being edited.
+    Quotes {
+        snapshot: DatabaseTransaction,
+        after_id: i32,
+        first: bool,
+    },
+    Footer,
+    Done,
+}
+
+struct ExportState {
+    db: Arc<DatabaseConnection>,
+    format: ExportFormat,
+    exported_at: String,
+    stage: Stage,
+}
+
+async fn header_chunk(
+    state: &ExportState,
+    snapshot: &DatabaseTransaction,
+) -> Result<Vec<u8>, AppError> {
+    if state.format == ExportFormat::Csv {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(CSV_COLUMNS).map_err(csv_error)?;
+        return writer
+            .into_inner()
+            .map_err(|e| AppError::IOError(e.into_error()));
+    }
+
+    let header = ExportHeader {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        exported_at: Some(state.exported_at.clone()),
+        categories: pg::export::fetch_category_records(snapshot).await?,
+    };
+    let mut chunk = serde_json::to_vec(&header)?;
+
+    match state.format {
+        // Reopen the header object so the quotes array becomes its last field.
+        ExportFormat::Json => {
+            chunk.pop();
+            chunk.extend_from_slice(b",\"quotes\":[");
+        }
+        _ => chunk.push(b'\n'),
+    }
+    Ok(chunk)
+}
+
+fn csv_error(e: csv::Error) -> AppError {
+    AppError::IOError(std::io::Error::other(e))
+}
+
+fn quotes_chunk(
+    state: &ExportState,
+    records: Vec<QuoteRecord>,
+    first: bool,
+) -> Result<Vec<u8>, AppError> {
+    let mut chunk = Vec::new();
+
+    match state.format {
+        ExportFormat::Json => {
+            for (index, record) in records.iter().enumerate() {
+                if !(first && index == 0) {
+                    chunk.push(b',');
+                }
+                serde_json::to_writer(&mut chunk, record)?;
+            }
+        }
+        ExportFormat::Ndjson => {
+            for record in &records {
+                serde_json::to_writer(&mut chunk, record)?;
+                chunk.push(b'\n');
+            }
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_writer(Vec::new());
+            for record in records {
+                writer
+                    .serialize(CsvQuoteRecord::from_record(record, &state.exported_at)?)
+                    .map_err(csv_error)?;
+            }
+            chunk = writer
+                .into_inner()
+                .map_err(|e| AppError::IOError(e.into_error()))?;
+        }
+    }
+
+    Ok(chunk)
+}
+
+async fn next_chunk(state: &mut ExportState) -> Result<Option<Vec<u8>>, AppError> {
+    match std::mem::replace(&mut state.stage, Stage::Done) {
+        Stage::Header => {
+            let snapshot = state
+                .db
+                .begin_with_config(
+                    Some(IsolationLevel::RepeatableRead),
+                    Some(AccessMode::ReadOnly),
+                )
+                .await?;
+            let chunk = header_chunk(state, &snapshot).await?;
+            state.stage = Stage::Quotes {
+                snapshot,
+                after_id: 0,
+                first: true,
+            };
+            Ok(Some(chunk))
+        }
+        Stage::Quotes {
+            snapshot,
+            after_id,
+            first,
+        } => {
+            let records =
+                pg::export::fetch_quote_records(&snapshot, after_id, EXPORT_BATCH_SIZE).await?;
+            let last_id = records.last().and_then(|record| record.id);
+
+            state.stage = match last_id {
+                Some(last_id) if records.len() as u64 == EXPORT_BATCH_SIZE => Stage::Quotes {
+                    snapshot,
+                    after_id: last_id,
+                    first: false,
+                },
+                _ => {
+                    snapshot.commit().await?;
+                    match state.format {
+                        ExportFormat::Json => Stage::Footer,
+                        _ => Stage::Done,
+                    }
+                }
+            };
+            quotes_chunk(state, records, first).map(Some)
+        }
+        Stage::Footer => Ok(Some(b"]}\n".to_vec())),
+        Stage::Done => Ok(None),
+    }
+}
+
+/// Streams the live corpus in `format` from a single snapshot, fetching
+/// quotes in batches so the export never holds more than one batch in memory.
+pub fn export_stream(
+    db: Arc<DatabaseConnection>,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<Bytes, AppError>> {
+    let state = ExportState {
+        db,
+        format,
+        exported_at: Utc::now().to_rfc3339(),
+        stage: Stage::Header,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        match next_chunk(&mut state).await {
+            Ok(Some(chunk)) => Some((Ok(Bytes::from(chunk)), state)),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), state)),
+        }
+    })
+}
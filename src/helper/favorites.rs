@@ -0,0 +1,81 @@
+use crate::db::queries::pg;
+use crate::models::data::{PageQuery, PagedResponse, ResponseQuote};
+use crate::models::errors::AppError;
+
+use actix_web::http::header::HeaderMap;
+use rand::seq::SliceRandom;
+use sea_orm::DatabaseConnection;
+
+/// Lets an integrating app keep separate favourites for each of its users
+/// under one API key.
+pub const END_USER_HEADER: &str = "X-End-User-Id";
+const MAX_END_USER_ID_LEN: usize = 128;
+
+/// Whose favourites a request reads or writes: the verified key, narrowed to
+/// one end user when the app names one.
+pub struct FavoriteOwner<'a> {
+    pub key_id: &'a str,
+    pub end_user_id: &'a str,
+}
+
+/// The end-user id from `X-End-User-Id`, or "" for the key's own list.
+pub fn end_user_id(headers: &HeaderMap) -> Result<&str, AppError> {
+    let Some(value) = headers.get(END_USER_HEADER) else {
+        return Ok("");
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest(format!("{} must be visible ASCII", END_USER_HEADER)))?
+        .trim();
+    match value.len() {
+        0 => Err(AppError::BadRequest(format!(
+            "{} must not be empty",
+            END_USER_HEADER
+        ))),
+        len if len > MAX_END_USER_ID_LEN => Err(AppError::BadRequest(format!(
+            "{} must be at most {} characters",
+            END_USER_HEADER, MAX_END_USER_ID_LEN
+        ))),
+        _ => Ok(value),
+    }
+}
+
+/// Saves a live quote. Returns false when it was already a favourite.
+pub async fn add_favorite(
+    db: &DatabaseConnection,
+    owner: &FavoriteOwner<'_>,
+    quote_id: i32,
+) -> Result<bool, AppError> {
+    pg::admin_quotes::ensure_quotes_exist(db, &[quote_id]).await?;
+    pg::favorites::add_favorite(db, owner.key_id, owner.end_user_id, quote_id).await
+}
+
+pub async fn remove_favorite(
+    db: &DatabaseConnection,
+    owner: &FavoriteOwner<'_>,
+    quote_id: i32,
+) -> Result<(), AppError> {
+    pg::favorites::remove_favorite(db, owner.key_id, owner.end_user_id, quote_id).await
+}
+
+pub async fn list_favorites(
+    db: &DatabaseConnection,
+    owner: &FavoriteOwner<'_>,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseQuote>, AppError> {
+    pg::favorites::fetch_favorites(db, owner.key_id, owner.end_user_id, page).await
+}
+
+pub async fn random_favorite(
+    db: &DatabaseConnection,
+    owner: &FavoriteOwner<'_>,
+) -> Result<ResponseQuote, AppError> {
+    let ids = pg::favorites::fetch_favorite_ids(db, owner.key_id, owner.end_user_id).await?;
+    let quote_id = ids
+        .choose(&mut rand::thread_rng())
+        .copied()
+        .ok_or_else(|| AppError::NotFound("No favourite quotes saved yet".to_string()))?;
+
+    pg::fetch_quote_by_id(db, quote_id).await
+}
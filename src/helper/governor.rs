@@ -7,7 +7,7 @@ use crate::utils::constants::RequestLimit;
 pub fn create_governor_config() -> GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware<QuantaInstant>>
 {
     GovernorConfigBuilder::default()
-        .requests_per_second(RequestLimit::RPS.as_u64())
+        .requests_per_second(RequestLimit::Rps.as_u64())
         .burst_size(RequestLimit::BurstSize.as_u64() as u32)
         .finish()
         .unwrap()
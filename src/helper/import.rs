@@ -0,0 +1,926 @@
+use crate::db::queries::pg;
+use crate::db::queries::pg::import::PlannedQuote;
+use crate::entities::quotes;
+use crate::helper::dedup::{self, DedupIndex, DedupMode};
+use crate::helper::lint::{self, LintInput};
+use crate::helper::{attribution, categories};
+use crate::models::data::{
+    AuthorRecord, CategoryRecord, ExportHeader, ImportIssue, ImportReport, LintIssue, LintSeverity,
+    QuoteRecord,
+};
+use crate::models::errors::AppError;
+use crate::utils::constants::EXPORT_SCHEMA_VERSION;
+
+use sea_orm::ActiveValue::Set;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+    Ndjson,
+    Yaml,
+}
+
+impl ImportFormat {
+    pub fn from_content_type(content_type: &str) -> Option<ImportFormat> {
+        if content_type.contains("csv") {
+            Some(ImportFormat::Csv)
+        } else if content_type.contains("ndjson") {
+            Some(ImportFormat::Ndjson)
+        } else if content_type.contains("json") {
+            Some(ImportFormat::Json)
+        } else if content_type.contains("yaml") {
+            Some(ImportFormat::Yaml)
+        } else {
+            None
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<ImportFormat> {
+        path.rsplit_once('.')
+            .and_then(|(_, extension)| extension.parse().ok())
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            "ndjson" | "jsonl" => Ok(ImportFormat::Ndjson),
+            "yaml" | "yml" => Ok(ImportFormat::Yaml),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported import format '{}', expected one of csv, json, ndjson, yaml",
+                other
+            ))),
+        }
+    }
+}
+
+/// Column order of CSV exports. Must match the fields of `CsvQuoteRecord`.
+pub const CSV_COLUMNS: [&str; 17] = [
+    "id",
+    "content",
+    "author",
+    "author_id",
+    "author_record",
+    "reference",
+    "references",
+    "language",
+    "attribution_status",
+    "grading_notes",
+    "graded_by",
+    "categories",
+    "translation_group_id",
+    "translation_original",
+    "qotd_dates",
+    "schema_version",
+    "exported_at",
+];
+
+/// A `QuoteRecord` flattened for CSV: lists are `;`-separated and nested
+/// records are JSON-encoded. Every column but `content`, `author` and
+/// `language` may be omitted on import.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CsvQuoteRecord {
+    #[serde(default)]
+    id: Option<i32>,
+    content: String,
+    author: String,
+    #[serde(default)]
+    author_id: Option<i32>,
+    #[serde(default)]
+    author_record: String,
+    #[serde(default)]
+    reference: String,
+    #[serde(default)]
+    references: String,
+    language: String,
+    #[serde(default)]
+    attribution_status: Option<String>,
+    #[serde(default)]
+    grading_notes: Option<String>,
+    #[serde(default)]
+    graded_by: Option<String>,
+    #[serde(default)]
+    categories: String,
+    #[serde(default)]
+    translation_group_id: Option<i32>,
+    #[serde(default)]
+    translation_original: Option<bool>,
+    #[serde(default)]
+    qotd_dates: String,
+    #[serde(default)]
+    schema_version: Option<u32>,
+    #[serde(default)]
+    exported_at: Option<String>,
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
+
+impl CsvQuoteRecord {
+    pub fn from_record(record: QuoteRecord, exported_at: &str) -> Result<Self, AppError> {
+        Ok(CsvQuoteRecord {
+            id: record.id,
+            content: record.content,
+            author: record.author,
+            author_id: record.author_id,
+            author_record: match &record.author_record {
+                Some(author) => serde_json::to_string(author)?,
+                None => String::new(),
+            },
+            reference: record.reference,
+            references: match record.references.is_empty() {
+                true => String::new(),
+                false => serde_json::to_string(&record.references)?,
+            },
+            language: record.language,
+            attribution_status: record.attribution_status,
+            grading_notes: record.grading_notes,
+            graded_by: record.graded_by,
+            categories: record.categories.join(";"),
+            translation_group_id: record.translation_group_id,
+            translation_original: Some(record.translation_original),
+            qotd_dates: record
+                .qotd_dates
+                .iter()
+                .map(|date| date.to_string())
+                .collect::<Vec<_>>()
+                .join(";"),
+            schema_version: Some(EXPORT_SCHEMA_VERSION),
+            exported_at: Some(exported_at.to_string()),
+        })
+    }
+
+    fn into_record(self) -> Result<QuoteRecord, String> {
+        if let Some(version) = self.schema_version {
+            check_schema_version(version).map_err(|e| e.to_string())?;
+        }
+
+        Ok(QuoteRecord {
+            id: self.id,
+            content: self.content,
+            author: self.author,
+            author_id: self.author_id,
+            author_record: match self.author_record.trim().is_empty() {
+                true => None,
+                false => Some(
+                    serde_json::from_str(&self.author_record)
+                        .map_err(|e| format!("Invalid author_record: {}", e))?,
+                ),
+            },
+            reference: self.reference,
+            references: match self.references.trim().is_empty() {
+                true => Vec::new(),
+                false => serde_json::from_str(&self.references)
+                    .map_err(|e| format!("Invalid references: {}", e))?,
+            },
+            language: self.language,
+            attribution_status: non_empty(self.attribution_status),
+            grading_notes: non_empty(self.grading_notes),
+            graded_by: non_empty(self.graded_by),
+            categories: split_list(&self.categories).map(str::to_string).collect(),
+            translation_group_id: self.translation_group_id,
+            translation_original: self.translation_original.unwrap_or(false),
+            qotd_dates: split_list(&self.qotd_dates)
+                .map(|date| {
+                    date.parse()
+                        .map_err(|e| format!("Invalid QOTD date '{}': {}", date, e))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+fn check_schema_version(version: u32) -> Result<(), AppError> {
+    match version <= EXPORT_SCHEMA_VERSION {
+        true => Ok(()),
+        false => Err(AppError::BadRequest(format!(
+            "Schema version {} is newer than the supported version {}",
+            version, EXPORT_SCHEMA_VERSION
+        ))),
+    }
+}
+
+/// A parsed import file: the export header when there is one, and its rows.
+pub struct ParsedImport {
+    pub header: Option<ExportHeader>,
+    pub rows: Vec<Result<QuoteRecord, String>>,
+}
+
+fn json_rows(rows: Vec<serde_json::Value>) -> Vec<Result<QuoteRecord, String>> {
+    rows.into_iter()
+        .map(|row| serde_json::from_value(row).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Parses an import file, accepting both plain lists of quotes and the
+/// documents written by the export. A malformed file fails as a whole; a
+/// malformed row becomes a row-level error so the rest can still be checked.
+pub fn parse_records(format: ImportFormat, body: &[u8]) -> Result<ParsedImport, AppError> {
+    match format {
+        ImportFormat::Csv => Ok(ParsedImport {
+            header: None,
+            rows: csv::Reader::from_reader(body)
+                .deserialize::<CsvQuoteRecord>()
+                .map(|row| {
+                    row.map_err(|e| e.to_string())
+                        .and_then(CsvQuoteRecord::into_record)
+                })
+                .collect(),
+        }),
+        ImportFormat::Json => {
+            let document: serde_json::Value = serde_json::from_slice(body)
+                .map_err(|e| AppError::BadRequest(format!("Invalid JSON import: {}", e)))?;
+            match document {
+                serde_json::Value::Array(rows) => Ok(ParsedImport {
+                    header: None,
+                    rows: json_rows(rows),
+                }),
+                serde_json::Value::Object(mut fields) => {
+                    let quotes = match fields.remove("quotes") {
+                        Some(serde_json::Value::Array(rows)) => rows,
+                        _ => {
+                            return Err(AppError::BadRequest(
+                                "A JSON import object needs a quotes array".to_string(),
+                            ))
+                        }
+                    };
+                    let header: ExportHeader =
+                        serde_json::from_value(serde_json::Value::Object(fields)).map_err(|e| {
+                            AppError::BadRequest(format!("Invalid export header: {}", e))
+                        })?;
+                    check_schema_version(header.schema_version)?;
+                    Ok(ParsedImport {
+                        header: Some(header),
+                        rows: json_rows(quotes),
+                    })
+                }
+                _ => Err(AppError::BadRequest(
+                    "A JSON import must be a list of quotes or an export document".to_string(),
+                )),
+            }
+        }
+        ImportFormat::Ndjson => {
+            let text = std::str::from_utf8(body)
+                .map_err(|e| AppError::BadRequest(format!("Invalid NDJSON import: {}", e)))?;
+            let mut lines = text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .peekable();
+
+            let header = match lines.peek() {
+                Some(line) if line.contains("\"schema_version\"") => {
+                    let header: ExportHeader = serde_json::from_str(line).map_err(|e| {
+                        AppError::BadRequest(format!("Invalid export header: {}", e))
+                    })?;
+                    check_schema_version(header.schema_version)?;
+                    lines.next();
+                    Some(header)
+                }
+                _ => None,
+            };
+
+            Ok(ParsedImport {
+                header,
+                rows: lines
+                    .map(|line| serde_json::from_str(line).map_err(|e| e.to_string()))
+                    .collect(),
+            })
+        }
+        ImportFormat::Yaml => {
+            let rows: Vec<serde_yaml::Value> = serde_yaml::from_slice(body)
+                .map_err(|e| AppError::BadRequest(format!("Invalid YAML import: {}", e)))?;
+            Ok(ParsedImport {
+                header: None,
+                rows: rows
+                    .into_iter()
+                    .map(|row| serde_yaml::from_value(row).map_err(|e| e.to_string()))
+                    .collect(),
+            })
+        }
+    }
+}
+
+fn required(field: &str, value: &str, problems: &mut Vec<String>) -> String {
+    let value = value.trim();
+    if value.is_empty() {
+        problems.push(format!("{} must not be empty", field));
+    }
+    value.to_string()
+}
+
+/// Reports, the first time an author record is seen, how it will resolve
+/// when that is not simply by its own name: aliases that already belong to
+/// another author are not added, and a new author that shares a name or
+/// alias with an earlier row of the file is merged into it.
+async fn check_author(
+    db: &DatabaseConnection,
+    author: &AuthorRecord,
+    row: usize,
+    resolved: &mut HashSet<String>,
+    file_authors: &mut HashMap<String, String>,
+    warnings: &mut Vec<ImportIssue>,
+) -> Result<(), AppError> {
+    let name = author.name.trim().to_string();
+    if !resolved.insert(name.clone()) {
+        return Ok(());
+    }
+
+    let author_id = pg::import::find_author(db, author).await?;
+    let mut owners: Vec<(String, i32)> = pg::import::fetch_alias_owners(db, &author.aliases)
+        .await?
+        .into_iter()
+        .filter(|(_, owner)| Some(*owner) != author_id)
+        .collect();
+    owners.sort();
+    for (alias, owner) in owners {
+        warnings.push(ImportIssue {
+            row,
+            message: format!(
+                "Alias '{}' already belongs to author {} and is not added to '{}'",
+                alias, owner, name
+            ),
+        });
+    }
+    if author_id.is_some() {
+        return Ok(());
+    }
+
+    let spellings: Vec<String> = std::iter::once(&author.name)
+        .chain(&author.aliases)
+        .map(|spelling| spelling.trim().to_string())
+        .collect();
+    match spellings.iter().find_map(|spelling| file_authors.get(spelling)) {
+        Some(earlier) => warnings.push(ImportIssue {
+            row,
+            message: format!(
+                "Author '{}' shares a name or alias with '{}' earlier in the file and is merged into it",
+                name, earlier
+            ),
+        }),
+        None => {
+            for spelling in spellings {
+                file_authors.insert(spelling, name.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates every row and, unless `dry_run` is set or any row is invalid,
+/// inserts the new quotes, authors, sources, categories and links in one
+/// transaction. Quotes whose normalised text already exists in the same
+/// language, in the database or earlier in the file, are reported as
+/// duplicates and skipped.
+pub async fn import_quotes(
+    db: &DatabaseConnection,
+    parsed: ParsedImport,
+    dry_run: bool,
+    changed_by: Option<&str>,
+) -> Result<ImportReport, AppError> {
+    let rows = parsed.rows;
+    let registry = pg::languages::fetch_languages(db, false).await?;
+    let known_codes: HashSet<&str> = registry.iter().map(|lang| lang.code.as_str()).collect();
+    let languages: HashMap<String, String> = registry
+        .iter()
+        .filter(|lang| lang.enabled)
+        .map(|lang| (lang.code.to_ascii_lowercase(), lang.code.clone()))
+        .collect();
+    let scripts: HashMap<&str, &str> = registry
+        .iter()
+        .map(|lang| (lang.code.as_str(), lang.script.as_str()))
+        .collect();
+
+    let mut author_ids: Vec<i32> = rows
+        .iter()
+        .flatten()
+        .filter(|record| record.author_record.is_none())
+        .filter_map(|record| record.author_id)
+        .collect();
+    author_ids.sort();
+    author_ids.dedup();
+    let known_authors = pg::import::fetch_existing_author_ids(db, &author_ids).await?;
+
+    let mut category_ids: HashMap<String, i32> = HashMap::new();
+    let mut category_slugs_by_name: HashMap<String, String> = HashMap::new();
+    for category in pg::categories::fetch_categories(db).await? {
+        category_slugs_by_name.insert(category.name.to_lowercase(), category.slug.clone());
+        category_ids.insert(category.slug, category.id);
+    }
+
+    let mut errors = Vec::new();
+    let mut new_categories: Vec<CategoryRecord> = Vec::new();
+    for category in parsed
+        .header
+        .map(|header| header.categories)
+        .unwrap_or_default()
+    {
+        if category_ids.contains_key(&category.slug) {
+            continue;
+        }
+        if category_slugs_by_name.contains_key(&category.name.to_lowercase()) {
+            errors.push(ImportIssue {
+                row: 0,
+                message: format!(
+                    "Category '{}' exists under another slug than '{}'",
+                    category.name, category.slug
+                ),
+            });
+            continue;
+        }
+        if let Some(language) = category
+            .names
+            .keys()
+            .find(|language| !known_codes.contains(language.as_str()))
+        {
+            errors.push(ImportIssue {
+                row: 0,
+                message: format!(
+                    "Category '{}' has a name in unknown language '{}'",
+                    category.slug, language
+                ),
+            });
+            continue;
+        }
+        category_slugs_by_name.insert(category.name.to_lowercase(), category.slug.clone());
+        new_categories.push(category);
+    }
+    for category in &new_categories {
+        if let Some(parent) = category.parent.as_deref().filter(|parent| {
+            !category_ids.contains_key(*parent) && !new_categories.iter().any(|c| c.slug == *parent)
+        }) {
+            errors.push(ImportIssue {
+                row: 0,
+                message: format!(
+                    "Category '{}' has unknown parent '{}'",
+                    category.slug, parent
+                ),
+            });
+        }
+    }
+
+    let mut row_languages: Vec<String> = rows
+        .iter()
+        .flatten()
+        .filter_map(|record| languages.get(&record.language.trim().to_ascii_lowercase()))
+        .cloned()
+        .collect();
+    row_languages.sort();
+    row_languages.dedup();
+    let mode = dedup::dedup_mode();
+    let threshold = dedup::dedup_threshold();
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let mut similar: DedupIndex<String> = DedupIndex::new();
+    for (id, language, content) in pg::import::fetch_contents(db, Some(&row_languages)).await? {
+        similar.insert(format!("quote {}", id), &language, &content);
+        seen.insert((language, dedup::normalize(&content)));
+    }
+
+    // A translation group id is kept when the file shares a quote with that
+    // group here, as when re-importing a partial export; otherwise it only
+    // links rows within the file and gets a new group.
+    let mut file_groups: Vec<i32> = rows
+        .iter()
+        .flatten()
+        .filter_map(|record| record.translation_group_id)
+        .collect();
+    file_groups.sort();
+    file_groups.dedup();
+    let members = pg::import::fetch_group_members(db, &file_groups).await?;
+    let member_texts: HashSet<(i32, String, String)> = members
+        .iter()
+        .map(|(group, language, content)| (*group, language.clone(), dedup::normalize(content)))
+        .collect();
+    let existing_groups: HashSet<i32> = rows
+        .iter()
+        .flatten()
+        .filter_map(|record| {
+            let group = record.translation_group_id?;
+            let language = languages.get(&record.language.trim().to_ascii_lowercase())?;
+            member_texts
+                .contains(&(group, language.clone(), dedup::normalize(&record.content)))
+                .then_some(group)
+        })
+        .collect();
+
+    let total = rows.len();
+    let mut planned = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut warnings = Vec::new();
+    let mut group_languages: HashSet<(i32, String)> = members
+        .into_iter()
+        .filter(|(group, ..)| existing_groups.contains(group))
+        .map(|(group, language, _)| (group, language))
+        .collect();
+    let mut resolved_authors: HashSet<String> = HashSet::new();
+    let mut file_authors: HashMap<String, String> = HashMap::new();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let record = match row {
+            Ok(record) => record,
+            Err(message) => {
+                errors.push(ImportIssue {
+                    row: row_number,
+                    message,
+                });
+                continue;
+            }
+        };
+
+        let mut problems = Vec::new();
+        let content = required("content", &record.content, &mut problems);
+        let author = required("author", &record.author, &mut problems);
+        let reference = record.reference.trim().to_string();
+        if reference.is_empty() && record.references.is_empty() {
+            problems.push("reference must not be empty".to_string());
+        }
+
+        let language = languages
+            .get(&record.language.trim().to_ascii_lowercase())
+            .cloned();
+        if language.is_none() {
+            problems.push(format!("Language '{}' is not supported", record.language));
+        }
+
+        let attribution_status = match record.attribution_status.as_deref() {
+            Some(value) => match attribution::parse_attribution_status(value) {
+                Ok(status) => status,
+                Err(e) => {
+                    problems.push(e.to_string());
+                    Default::default()
+                }
+            },
+            None => Default::default(),
+        };
+
+        match (&record.author_record, record.author_id) {
+            (Some(author_record), _) if author_record.name.trim().is_empty() => {
+                problems.push("author_record.name must not be empty".to_string());
+            }
+            (None, Some(author_id)) if !known_authors.contains(&author_id) => {
+                problems.push(format!("Author {} does not exist", author_id));
+            }
+            _ => {}
+        }
+
+        if record
+            .references
+            .iter()
+            .any(|source| source.title.trim().is_empty())
+        {
+            problems.push("Every reference needs a title".to_string());
+        }
+
+        let mut category_slugs = Vec::new();
+        let mut row_categories: Vec<CategoryRecord> = Vec::new();
+        for category in &record.categories {
+            let name = category.trim();
+            let slug = if category_ids.contains_key(name)
+                || new_categories.iter().any(|new| new.slug == name)
+            {
+                name.to_string()
+            } else {
+                category_slugs_by_name
+                    .get(&name.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| categories::slugify(name))
+            };
+            if slug.is_empty() {
+                problems.push(format!("Cannot derive a category slug from '{}'", name));
+                continue;
+            }
+            if !category_ids.contains_key(&slug)
+                && !new_categories
+                    .iter()
+                    .chain(&row_categories)
+                    .any(|new| new.slug == slug)
+            {
+                row_categories.push(CategoryRecord {
+                    slug: slug.clone(),
+                    name: name.to_string(),
+                    parent: None,
+                    names: Default::default(),
+                });
+            }
+            category_slugs.push(slug);
+        }
+
+        let (lint_errors, lint_warnings): (Vec<LintIssue>, Vec<LintIssue>) =
+            lint::lint(&LintInput {
+                content: &content,
+                author: &author,
+                reference: Some(&reference),
+                has_sources: !record.references.is_empty(),
+                script: language
+                    .as_deref()
+                    .and_then(|language| scripts.get(language).copied())
+                    .unwrap_or_default(),
+            })
+            .into_iter()
+            .partition(|issue| issue.severity == LintSeverity::Error);
+        if !lint_errors.is_empty() {
+            problems.push(lint::describe(&lint_errors));
+        }
+
+        let Some(language) = language.filter(|_| problems.is_empty()) else {
+            errors.push(ImportIssue {
+                row: row_number,
+                message: problems.join("; "),
+            });
+            continue;
+        };
+
+        if !seen.insert((language.clone(), dedup::normalize(&content))) {
+            duplicates.push(ImportIssue {
+                row: row_number,
+                message: format!("Quote already exists in '{}'", language),
+            });
+            continue;
+        }
+
+        let matches = similar.find_similar(&language, &content, threshold);
+        similar.insert(format!("row {}", row_number), &language, &content);
+        if !matches.is_empty() {
+            let issue = ImportIssue {
+                row: row_number,
+                message: format!(
+                    "Possible duplicate of {}",
+                    dedup::describe_matches(&matches)
+                ),
+            };
+            match mode {
+                DedupMode::Warn => warnings.push(issue),
+                DedupMode::Reject => {
+                    errors.push(issue);
+                    continue;
+                }
+            }
+        }
+
+        if let Some(group) = record.translation_group_id {
+            if !group_languages.insert((group, language.clone())) {
+                errors.push(ImportIssue {
+                    row: row_number,
+                    message: format!(
+                        "Translation group {} already has a '{}' quote",
+                        group, language
+                    ),
+                });
+                continue;
+            }
+        }
+
+        if !lint_warnings.is_empty() {
+            warnings.push(ImportIssue {
+                row: row_number,
+                message: lint::describe(&lint_warnings),
+            });
+        }
+
+        if let Some(author_record) = &record.author_record {
+            check_author(
+                db,
+                author_record,
+                row_number,
+                &mut resolved_authors,
+                &mut file_authors,
+                &mut warnings,
+            )
+            .await?;
+        }
+
+        new_categories.extend(row_categories);
+        planned.push(PlannedQuote {
+            quote: quotes::ActiveModel {
+                quote: Set(content),
+                author: Set(author),
+                author_id: Set(record.author_id.filter(|_| record.author_record.is_none())),
+                reference: Set(Some(reference).filter(|reference| !reference.is_empty())),
+                language: Set(language),
+                attribution_status: Set(attribution_status),
+                grading_notes: Set(record.grading_notes),
+                graded_by: Set(record.graded_by),
+                ..Default::default()
+            },
+            author: record.author_record,
+            references: record.references,
+            category_slugs,
+            translation_group: record.translation_group_id,
+            translation_original: record.translation_original,
+            qotd_dates: record.qotd_dates,
+        });
+    }
+
+    let valid = planned.len();
+    let created_categories = new_categories
+        .iter()
+        .map(|category| category.slug.clone())
+        .collect();
+
+    let commit = !dry_run && errors.is_empty();
+    let quote_ids = match commit {
+        true => {
+            pg::import::apply_import(
+                db,
+                &new_categories,
+                category_ids,
+                &existing_groups,
+                planned,
+                changed_by,
+            )
+            .await?
+        }
+        false => Vec::new(),
+    };
+
+    Ok(ImportReport {
+        dry_run,
+        committed: commit,
+        rows: total,
+        valid,
+        quote_ids,
+        created_categories,
+        duplicates,
+        warnings,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(parsed: ParsedImport) -> Vec<QuoteRecord> {
+        parsed
+            .rows
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .expect("every row parses")
+    }
+
+    #[test]
+    fn csv_splits_lists_and_decodes_nested_fields() {
+        let body = "content,author,language,categories,qotd_dates,author_record\n\
+                    Seek knowledge,Rumi,en, ethics ; ;knowledge,2024-01-02;2024-03-04,\"{\"\"name\"\":\"\"Rumi\"\",\"\"aliases\"\":[\"\"Mawlana\"\"]}\"\n";
+        let parsed = parse_records(ImportFormat::Csv, body.as_bytes()).unwrap();
+        assert!(parsed.header.is_none());
+
+        let record = &records(parsed)[0];
+        assert_eq!(record.categories, vec!["ethics", "knowledge"]);
+        assert_eq!(record.qotd_dates.len(), 2);
+        assert_eq!(
+            record
+                .author_record
+                .as_ref()
+                .map(|author| author.aliases.clone()),
+            Some(vec!["Mawlana".to_string()])
+        );
+        assert!(record.references.is_empty());
+    }
+
+    #[test]
+    fn csv_reports_bad_rows_individually() {
+        let body = "content,author,language,qotd_dates\n\
+                    One,Rumi,en,not-a-date\n\
+                    Two,Rumi,en,\n";
+        let rows = parse_records(ImportFormat::Csv, body.as_bytes())
+            .unwrap()
+            .rows;
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].as_ref().unwrap_err().contains("Invalid QOTD date"));
+        assert!(rows[1].is_ok());
+    }
+
+    #[test]
+    fn csv_rejects_newer_schema_versions_per_row() {
+        let body = format!(
+            "content,author,language,schema_version\nOne,Rumi,en,{}\n",
+            EXPORT_SCHEMA_VERSION + 1
+        );
+        let rows = parse_records(ImportFormat::Csv, body.as_bytes())
+            .unwrap()
+            .rows;
+        assert!(rows[0].is_err());
+    }
+
+    #[test]
+    fn json_accepts_a_plain_list() {
+        let body = br#"[{"content": "One", "author": "Rumi", "language": "en"}, {"author": "x"}]"#;
+        let parsed = parse_records(ImportFormat::Json, body).unwrap();
+        assert!(parsed.header.is_none());
+        assert!(parsed.rows[0].is_ok());
+        assert!(parsed.rows[1].is_err());
+    }
+
+    #[test]
+    fn json_accepts_an_export_document() {
+        let body = br#"{"schema_version": 1, "categories": [{"slug": "ethics", "name": "Ethics"}],
+                        "quotes": [{"content": "One", "author": "Rumi", "language": "en"}]}"#;
+        let parsed = parse_records(ImportFormat::Json, body).unwrap();
+        let header = parsed.header.as_ref().unwrap();
+        assert_eq!(header.categories[0].slug, "ethics");
+        assert_eq!(records(parsed).len(), 1);
+    }
+
+    #[test]
+    fn json_rejects_malformed_documents() {
+        assert!(parse_records(ImportFormat::Json, b"{not json").is_err());
+        assert!(parse_records(ImportFormat::Json, br#"{"schema_version": 1}"#).is_err());
+        assert!(parse_records(ImportFormat::Json, b"42").is_err());
+        let newer = format!(
+            r#"{{"schema_version": {}, "quotes": []}}"#,
+            EXPORT_SCHEMA_VERSION + 1
+        );
+        assert!(parse_records(ImportFormat::Json, newer.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn ndjson_reads_an_optional_header_line() {
+        let body = b"{\"schema_version\": 1}\n\n{\"content\": \"One\", \"author\": \"Rumi\", \"language\": \"en\"}\n";
+        let parsed = parse_records(ImportFormat::Ndjson, body).unwrap();
+        assert!(parsed.header.is_some());
+        assert_eq!(records(parsed).len(), 1);
+
+        let body =
+            b"{\"content\": \"One\", \"author\": \"Rumi\", \"language\": \"en\"}\nnot json\n";
+        let parsed = parse_records(ImportFormat::Ndjson, body).unwrap();
+        assert!(parsed.header.is_none());
+        assert!(parsed.rows[0].is_ok());
+        assert!(parsed.rows[1].is_err());
+    }
+
+    #[test]
+    fn yaml_reads_a_list_of_quotes() {
+        let body = b"- content: One\n  author: Rumi\n  language: en\n  categories: [ethics]\n";
+        let record = &records(parse_records(ImportFormat::Yaml, body).unwrap())[0];
+        assert_eq!(record.categories, vec!["ethics"]);
+        assert!(parse_records(ImportFormat::Yaml, b"content: One").is_err());
+    }
+
+    #[test]
+    fn csv_round_trips_an_exported_record() {
+        let record = QuoteRecord {
+            id: Some(3),
+            content: "One".to_string(),
+            author: "Rumi".to_string(),
+            author_id: None,
+            author_record: None,
+            reference: "Masnavi".to_string(),
+            references: Vec::new(),
+            language: "en".to_string(),
+            attribution_status: Some("verified".to_string()),
+            grading_notes: None,
+            graded_by: None,
+            categories: vec!["ethics".to_string(), "love".to_string()],
+            translation_group_id: Some(9),
+            translation_original: true,
+            qotd_dates: vec!["2024-01-02".parse().unwrap()],
+        };
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer
+            .serialize(CsvQuoteRecord::from_record(record, "2024-01-03T00:00:00Z").unwrap())
+            .unwrap();
+        let body = writer.into_inner().unwrap();
+
+        let parsed = &records(parse_records(ImportFormat::Csv, &body).unwrap())[0];
+        assert_eq!(parsed.id, Some(3));
+        assert_eq!(parsed.categories, vec!["ethics", "love"]);
+        assert_eq!(parsed.translation_group_id, Some(9));
+        assert!(parsed.translation_original);
+        assert_eq!(parsed.attribution_status.as_deref(), Some("verified"));
+    }
+
+    #[test]
+    fn format_is_detected_from_content_type_and_path() {
+        assert_eq!(
+            ImportFormat::from_content_type("application/x-ndjson"),
+            Some(ImportFormat::Ndjson)
+        );
+        assert_eq!(
+            ImportFormat::from_content_type("application/json; charset=utf-8"),
+            Some(ImportFormat::Json)
+        );
+        assert_eq!(
+            ImportFormat::from_path("dump.YML"),
+            Some(ImportFormat::Yaml)
+        );
+        assert_eq!(ImportFormat::from_path("dump"), None);
+    }
+}
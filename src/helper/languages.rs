@@ -0,0 +1,228 @@
+use crate::db::queries::pg;
+use crate::entities::languages;
+use crate::models::data::ResponseLanguage;
+use crate::models::errors::AppError;
+use crate::utils::constants::DEFAULT_LANGUAGE;
+
+use actix_web::http::header::{self, HeaderMap};
+use sea_orm::DatabaseConnection;
+
+pub async fn resolve_language(
+    db: &DatabaseConnection,
+    code: &str,
+) -> Result<languages::Model, AppError> {
+    pg::languages::fetch_language(db, code)
+        .await?
+        .filter(|lang| lang.enabled)
+        .ok_or_else(|| AppError::NotFound(format!("Language '{}' is not supported", code)))
+}
+
+/// Like `resolve_language`, but for codes submitted in a request body, where
+/// an unknown language is a client error rather than a missing resource.
+pub async fn validate_language(db: &DatabaseConnection, code: &str) -> Result<String, AppError> {
+    match resolve_language(db, code).await {
+        Ok(lang) => Ok(lang.code),
+        Err(AppError::NotFound(msg)) => Err(AppError::BadRequest(msg)),
+        Err(e) => Err(e),
+    }
+}
+
+/// The ISO 15924 script `code` is written in, empty for unknown codes.
+pub async fn language_script(db: &DatabaseConnection, code: &str) -> Result<String, AppError> {
+    Ok(pg::languages::fetch_language(db, code)
+        .await?
+        .map(|lang| lang.script)
+        .unwrap_or_default())
+}
+
+pub fn accept_language(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut tags: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            if tag.is_empty() {
+                return None;
+            }
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (quality > 0.0).then(|| (tag.to_string(), quality))
+        })
+        .collect();
+
+    tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+fn primary_subtag(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+fn match_language<'a>(
+    available: &'a [languages::Model],
+    tag: &str,
+) -> Option<&'a languages::Model> {
+    available
+        .iter()
+        .find(|lang| lang.code.eq_ignore_ascii_case(tag))
+        .or_else(|| {
+            available
+                .iter()
+                .find(|lang| primary_subtag(&lang.code).eq_ignore_ascii_case(primary_subtag(tag)))
+        })
+}
+
+/// Picks the best enabled language for an `Accept-Language` header, falling
+/// back to the default language when nothing matches.
+pub async fn negotiate_language(
+    db: &DatabaseConnection,
+    accept_language: Option<&str>,
+) -> Result<languages::Model, AppError> {
+    let available = pg::languages::fetch_languages(db, true).await?;
+
+    for tag in parse_accept_language(accept_language.unwrap_or_default()) {
+        if tag == "*" {
+            break;
+        }
+        if let Some(lang) = match_language(&available, &tag) {
+            return Ok(lang.clone());
+        }
+    }
+
+    resolve_language(db, DEFAULT_LANGUAGE).await
+}
+
+/// Follows `fallback_code` links from `first` (e.g. ur-Arab -> ur-Latn),
+/// ending with the default language. Disabled languages and cycles end the
+/// walk early.
+pub async fn fallback_chain(
+    db: &DatabaseConnection,
+    first: languages::Model,
+) -> Result<Vec<languages::Model>, AppError> {
+    let mut chain = vec![first];
+
+    while let Some(code) = chain.last().and_then(|lang| lang.fallback_code.clone()) {
+        if chain.iter().any(|lang| lang.code == code) {
+            break;
+        }
+        match resolve_language(db, &code).await {
+            Ok(lang) => chain.push(lang),
+            Err(_) => break,
+        }
+    }
+
+    if !chain.iter().any(|lang| lang.code == DEFAULT_LANGUAGE) {
+        if let Ok(default) = resolve_language(db, DEFAULT_LANGUAGE).await {
+            chain.push(default);
+        }
+    }
+
+    Ok(chain)
+}
+
+/// Resolves the language chain for a request. An explicit path language is
+/// served as asked or not at all; only a negotiated language falls back.
+pub async fn request_language_chain(
+    db: &DatabaseConnection,
+    code: Option<&str>,
+    accept_language: Option<&str>,
+) -> Result<Vec<languages::Model>, AppError> {
+    match code {
+        Some(code) => Ok(vec![resolve_language(db, code).await?]),
+        None => fallback_chain(db, negotiate_language(db, accept_language).await?).await,
+    }
+}
+
+pub async fn list_languages(db: &DatabaseConnection) -> Result<Vec<ResponseLanguage>, AppError> {
+    let counts = pg::languages::fetch_quote_counts(db).await?;
+
+    Ok(pg::languages::fetch_languages(db, true)
+        .await?
+        .into_iter()
+        .map(|lang| ResponseLanguage {
+            quote_count: counts.get(&lang.code).copied().unwrap_or(0),
+            has_qotd: lang.qotd_enabled,
+            code: lang.code,
+            name: lang.name,
+            native_name: lang.native_name,
+            script: lang.script,
+            direction: lang.direction,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(code: &str) -> languages::Model {
+        languages::Model {
+            code: code.to_string(),
+            name: code.to_string(),
+            native_name: code.to_string(),
+            script: "Latn".to_string(),
+            direction: "ltr".to_string(),
+            enabled: true,
+            qotd_enabled: true,
+            fallback_code: None,
+        }
+    }
+
+    #[test]
+    fn parse_accept_language_orders_by_quality() {
+        assert_eq!(
+            parse_accept_language("en;q=0.5, ar, ur-Latn;q=0.8"),
+            vec!["ar", "ur-Latn", "en"]
+        );
+    }
+
+    #[test]
+    fn parse_accept_language_keeps_header_order_for_equal_quality() {
+        assert_eq!(parse_accept_language("de, fr, en"), vec!["de", "fr", "en"]);
+    }
+
+    #[test]
+    fn parse_accept_language_drops_rejected_and_empty_tags() {
+        assert_eq!(parse_accept_language("ar;q=0, , en ;q=0.3,"), vec!["en"]);
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn parse_accept_language_treats_bad_quality_as_one() {
+        assert_eq!(
+            parse_accept_language("en;q=0.9, ar;q=abc"),
+            vec!["ar", "en"]
+        );
+    }
+
+    #[test]
+    fn match_language_prefers_exact_code() {
+        let available = [language("ur-Latn"), language("ur-Arab"), language("en")];
+        assert_eq!(
+            match_language(&available, "UR-ARAB").map(|lang| lang.code.as_str()),
+            Some("ur-Arab")
+        );
+    }
+
+    #[test]
+    fn match_language_falls_back_to_primary_subtag() {
+        let available = [language("ur-Latn"), language("ur-Arab"), language("en")];
+        assert_eq!(
+            match_language(&available, "ur-PK").map(|lang| lang.code.as_str()),
+            Some("ur-Latn")
+        );
+        assert_eq!(
+            match_language(&available, "en-GB").map(|lang| lang.code.as_str()),
+            Some("en")
+        );
+        assert!(match_language(&available, "fr-FR").is_none());
+    }
+}
@@ -0,0 +1,402 @@
+use crate::db::queries::pg;
+use crate::helper::{arabic, languages};
+use crate::models::data::{
+    LintFinding, LintIssue, LintQuery, LintReport, LintSeverity, PageQuery, PagedResponse,
+};
+use crate::models::errors::AppError;
+
+use sea_orm::DatabaseConnection;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Quotes checked per query while building the report.
+const BATCH_SIZE: u64 = 1000;
+
+/// ISO 15924 code of the languages `LatinInArabic` applies to.
+const ARABIC_SCRIPT: &str = "Arab";
+
+/// Opening and closing marks that must pair up within a quote. German
+/// „…“ reuses the English opening mark as a closer, so it is left alone.
+const QUOTATION_PAIRS: [(char, char); 4] = [('“', '”'), ('«', '»'), ('‹', '›'), ('「', '」')];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    TrailingWhitespace,
+    RepeatedWhitespace,
+    UnbalancedQuotes,
+    LatinInArabic,
+    MissingReference,
+}
+
+impl Rule {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::TrailingWhitespace => "whitespace/trailing",
+            Rule::RepeatedWhitespace => "whitespace/repeated",
+            Rule::UnbalancedQuotes => "quotes/unbalanced",
+            Rule::LatinInArabic => "script/latin-in-arabic",
+            Rule::MissingReference => "reference/missing",
+        }
+    }
+
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            Rule::MissingReference => LintSeverity::Error,
+            Rule::TrailingWhitespace | Rule::UnbalancedQuotes | Rule::LatinInArabic => {
+                LintSeverity::Warning
+            }
+            Rule::RepeatedWhitespace => LintSeverity::Info,
+        }
+    }
+
+    fn issue(self, field: &'static str, message: String) -> LintIssue {
+        LintIssue {
+            rule: self.id(),
+            severity: self.severity(),
+            field,
+            message,
+        }
+    }
+}
+
+/// The fields of a quote the rules look at. `has_sources` tells whether
+/// structured references stand in for a missing `reference`; `script` is the
+/// ISO 15924 code of the quote's language.
+pub struct LintInput<'a> {
+    pub content: &'a str,
+    pub author: &'a str,
+    pub reference: Option<&'a str>,
+    pub has_sources: bool,
+    pub script: &'a str,
+}
+
+fn unbalanced_quotes(text: &str) -> Option<String> {
+    let straight = text.matches('"').count();
+    if straight % 2 == 1 {
+        return Some(format!("has {} straight double quotes", straight));
+    }
+    if text.contains('„') {
+        return None;
+    }
+
+    QUOTATION_PAIRS.iter().find_map(|(open, close)| {
+        let opened = text.matches(*open).count();
+        let closed = text.matches(*close).count();
+        (opened != closed).then(|| format!("has {} {} but {} {}", opened, open, closed, close))
+    })
+}
+
+pub fn lint(input: &LintInput) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    let fields = [
+        ("content", Some(input.content)),
+        ("author", Some(input.author)),
+        ("reference", input.reference),
+    ];
+    for (field, value) in fields {
+        let Some(value) = value else {
+            continue;
+        };
+        if value != value.trim() {
+            issues.push(Rule::TrailingWhitespace.issue(
+                field,
+                format!("{} has leading or trailing whitespace", field),
+            ));
+        }
+        if value.contains("  ") {
+            issues.push(
+                Rule::RepeatedWhitespace.issue(field, format!("{} has repeated spaces", field)),
+            );
+        }
+    }
+
+    if let Some(problem) = unbalanced_quotes(input.content) {
+        issues.push(Rule::UnbalancedQuotes.issue("content", format!("content {}", problem)));
+    }
+
+    if input.script == ARABIC_SCRIPT
+        && input.content.chars().any(arabic::is_arabic_letter)
+        && input.content.chars().any(|c| c.is_ascii_alphabetic())
+    {
+        issues.push(Rule::LatinInArabic.issue(
+            "content",
+            "Arabic-script content contains Latin letters".to_string(),
+        ));
+    }
+
+    let reference = input.reference.map(str::trim).unwrap_or_default();
+    if reference.is_empty() && !input.has_sources {
+        issues
+            .push(Rule::MissingReference.issue("reference", "quote has no reference".to_string()));
+    }
+
+    issues
+}
+
+/// Renders issues as `rule: message; rule: message`.
+pub fn describe(issues: &[LintIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("{}: {}", issue.rule, issue.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Lints a quote about to be written. Error-level findings reject the write;
+/// the remaining findings are returned so the caller can pass them on.
+pub fn check(input: &LintInput) -> Result<Vec<LintIssue>, AppError> {
+    let (errors, warnings): (Vec<LintIssue>, Vec<LintIssue>) = lint(input)
+        .into_iter()
+        .partition(|issue| issue.severity == LintSeverity::Error);
+
+    match errors.is_empty() {
+        true => Ok(warnings),
+        false => Err(AppError::BadRequest(format!(
+            "Quote failed lint: {}",
+            describe(&errors)
+        ))),
+    }
+}
+
+/// Findings over every live quote, as of a change cursor and the language
+/// scripts the rules saw. `checked` counts the quotes per language.
+struct CachedReport {
+    cursor: i64,
+    scripts: HashMap<String, String>,
+    checked: HashMap<String, u64>,
+    findings: Vec<LintFinding>,
+}
+
+/// The last report built, so paging through it does not lint every quote
+/// again for each page. Language filters are applied to it per request.
+static REPORT_CACHE: Mutex<Option<Arc<CachedReport>>> = Mutex::new(None);
+
+fn lock_cache() -> MutexGuard<'static, Option<Arc<CachedReport>>> {
+    REPORT_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Runs every rule over the live quotes, in batches so the table never has to
+/// fit in memory at once.
+async fn build_report(
+    db: &DatabaseConnection,
+    cursor: i64,
+    scripts: HashMap<String, String>,
+) -> Result<CachedReport, AppError> {
+    let mut checked: HashMap<String, u64> = HashMap::new();
+    let mut findings = Vec::new();
+    let mut after_id = 0;
+
+    loop {
+        let batch = pg::lint::fetch_quote_batch(db, after_id, BATCH_SIZE).await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after_id = last.id;
+
+        let ids: Vec<i32> = batch.iter().map(|quote| quote.id).collect();
+        let with_sources = pg::lint::fetch_ids_with_sources(db, &ids).await?;
+
+        for quote in batch {
+            *checked.entry(quote.language.clone()).or_default() += 1;
+            let issues = lint(&LintInput {
+                content: &quote.quote,
+                author: &quote.author,
+                reference: quote.reference.as_deref(),
+                has_sources: with_sources.contains(&quote.id),
+                script: scripts
+                    .get(&quote.language)
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            });
+            findings.extend(issues.into_iter().map(|issue| LintFinding {
+                quote_id: quote.id,
+                language: quote.language.clone(),
+                issue,
+            }));
+        }
+    }
+
+    Ok(CachedReport {
+        cursor,
+        scripts,
+        checked,
+        findings,
+    })
+}
+
+/// The cached report when no quote or language script changed since it was
+/// built, or a new one. The build runs with the cache unlocked.
+async fn current_report(db: &DatabaseConnection) -> Result<Arc<CachedReport>, AppError> {
+    // Read the version first, so a change racing the build is picked up by
+    // the next report.
+    let cursor = pg::changes::fetch_latest_cursor(db, None).await?;
+    let scripts = pg::languages::fetch_scripts(db).await?;
+
+    if let Some(cached) = lock_cache()
+        .as_ref()
+        .filter(|cached| cached.cursor == cursor && cached.scripts == scripts)
+    {
+        return Ok(Arc::clone(cached));
+    }
+
+    let report = Arc::new(build_report(db, cursor, scripts).await?);
+    let mut cache = lock_cache();
+    if cache
+        .as_ref()
+        .is_none_or(|cached| cached.cursor <= report.cursor)
+    {
+        *cache = Some(Arc::clone(&report));
+    }
+    Ok(report)
+}
+
+/// Lint findings over the live quotes, filtered and paged. The findings are
+/// rebuilt only when a quote or a language's script changed since the last
+/// report.
+pub async fn lint_report(
+    db: &DatabaseConnection,
+    query: &LintQuery,
+    page: &PageQuery,
+) -> Result<LintReport, AppError> {
+    let language = match &query.lang {
+        Some(lang) => Some(languages::validate_language(db, lang).await?),
+        None => None,
+    };
+    let cached = current_report(db).await?;
+    let checked = match &language {
+        Some(language) => cached.checked.get(language).copied().unwrap_or_default(),
+        None => cached.checked.values().sum(),
+    };
+
+    let offset = ((page.page() - 1) * page.per_page()) as usize;
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut items = Vec::new();
+    let mut total = 0;
+
+    let matching = cached.findings.iter().filter(|finding| {
+        language
+            .as_deref()
+            .is_none_or(|language| language == finding.language)
+            && query
+                .rule
+                .as_deref()
+                .is_none_or(|rule| rule == finding.issue.rule)
+            && query
+                .severity
+                .is_none_or(|severity| severity == finding.issue.severity)
+    });
+    for finding in matching {
+        *counts.entry(finding.issue.rule.to_string()).or_default() += 1;
+        total += 1;
+        if total > offset as u64 && items.len() < page.per_page() as usize {
+            items.push(finding.clone());
+        }
+    }
+
+    Ok(LintReport {
+        checked,
+        counts,
+        issues: PagedResponse {
+            items,
+            page: page.page(),
+            per_page: page.per_page(),
+            total,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(content: &'a str, script: &'a str) -> LintInput<'a> {
+        LintInput {
+            content,
+            author: "Ibn al-Qayyim",
+            reference: Some("Madarij al-Salikin"),
+            has_sources: false,
+            script,
+        }
+    }
+
+    fn rules(input: &LintInput) -> Vec<&'static str> {
+        lint(input).into_iter().map(|issue| issue.rule).collect()
+    }
+
+    #[test]
+    fn clean_quote_has_no_findings() {
+        assert!(rules(&input("Knowledge is light.", "Latn")).is_empty());
+    }
+
+    #[test]
+    fn flags_trailing_and_repeated_whitespace_per_field() {
+        let issues = lint(&LintInput {
+            author: "Ibn  al-Qayyim ",
+            ..input("Knowledge is light.", "Latn")
+        });
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.rule, issue.field))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Rule::TrailingWhitespace.id(), "author"),
+                (Rule::RepeatedWhitespace.id(), "author"),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_unbalanced_quotation_marks() {
+        assert_eq!(
+            rules(&input("He said \"patience", "Latn")),
+            vec![Rule::UnbalancedQuotes.id()]
+        );
+        assert_eq!(
+            rules(&input("He said “patience", "Latn")),
+            vec![Rule::UnbalancedQuotes.id()]
+        );
+        assert!(rules(&input("Er sagte „Geduld“", "Latn")).is_empty());
+        assert!(rules(&input("Il a dit « patience »", "Latn")).is_empty());
+    }
+
+    #[test]
+    fn latin_in_arabic_only_applies_to_arabic_script_languages() {
+        let mixed = "العلم نور (Knowledge is light)";
+        assert_eq!(rules(&input(mixed, "Arab")), vec![Rule::LatinInArabic.id()]);
+        assert!(rules(&input(mixed, "Latn")).is_empty());
+        assert!(rules(&input("العلم نور", "Arab")).is_empty());
+    }
+
+    #[test]
+    fn sources_stand_in_for_a_missing_reference() {
+        let missing = LintInput {
+            reference: Some(""),
+            ..input("Knowledge is light.", "Latn")
+        };
+        assert_eq!(rules(&missing), vec![Rule::MissingReference.id()]);
+        assert!(check(&missing).is_err());
+
+        let with_sources = LintInput {
+            reference: None,
+            has_sources: true,
+            ..input("Knowledge is light.", "Latn")
+        };
+        assert!(rules(&with_sources).is_empty());
+    }
+
+    #[test]
+    fn check_returns_non_blocking_findings() {
+        let warnings = check(&input("Knowledge  is light.", "Latn")).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, LintSeverity::Info);
+        assert_eq!(
+            describe(&warnings),
+            "whitespace/repeated: content has repeated spaces"
+        );
+    }
+}
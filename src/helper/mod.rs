@@ -1,3 +1,22 @@
+pub mod admin_quotes;
+pub mod arabic;
+pub mod attribution;
+pub mod authors;
+pub mod bundles;
+pub mod categories;
+pub mod changes;
+pub mod citation;
+pub mod dedup;
+pub mod export;
+pub mod favorites;
 pub mod governor;
+pub mod import;
+pub mod languages;
+pub mod lint;
 pub mod oauth;
 pub mod quotes;
+pub mod ratings;
+pub mod reports;
+pub mod submissions;
+pub mod translations;
+pub mod transliteration;
@@ -1,4 +1,4 @@
-use crate::models::data::{AppState, UnkeyApiId};
+use crate::models::data::{ApiKeyId, AppState, UnkeyApiId};
 use crate::models::errors::AppError::{self, ApiKeyError};
 
 use crate::utils;
@@ -8,7 +8,7 @@ use actix_web::{
     dev::{ServiceRequest, ServiceResponse},
     error::{Error, ErrorInternalServerError, ErrorUnauthorized},
     middleware::Next,
-    web,
+    web, HttpMessage,
 };
 
 use log::{error, info};
@@ -53,6 +53,9 @@ async fn verfiy_api(req: &ServiceRequest) -> Result<VerifyKeyResponse, Error> {
             if !response.valid {
                 return Err(ErrorUnauthorized("Invalid API key"));
             }
+            if let Some(key_id) = &response.key_id {
+                req.extensions_mut().insert(ApiKeyId(key_id.clone()));
+            }
             Ok(response)
         }
         Err(err) => {
@@ -132,6 +135,14 @@ async fn check_rate_limit(response: VerifyKeyResponse) -> Result<bool, AppError>
     Ok(true)
 }
 
+/// The id that `verfiy_api` stored for the request's key, for endpoints that
+/// keep data per key.
+pub fn verified_key_id(key: &Option<web::ReqData<ApiKeyId>>) -> Result<&str, AppError> {
+    key.as_deref()
+        .map(|key| key.0.as_str())
+        .ok_or_else(|| ApiKeyError("API key has no id".to_string()))
+}
+
 fn extract_authorization_header(headers: &HeaderMap) -> Result<String, Error> {
     headers
         .get("Authorization")
@@ -1,7 +1,14 @@
 use crate::db::queries::pg;
 use crate::db::queries::rds;
+use crate::entities::languages;
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::helper::arabic;
+use crate::helper::attribution;
+use crate::helper::ratings;
+use crate::helper::translations;
+use crate::helper::transliteration::{self, UrduScript};
 use crate::models::data;
-use crate::models::data::ResponseQuote;
+use crate::models::data::{DisplayQuery, QuotePayload, ResponseQuote};
 use crate::models::errors::AppError;
 use crate::utils;
 
@@ -10,15 +17,54 @@ use futures_util::future;
 use log::{error, info};
 use redis::AsyncCommands;
 use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+
+/// When `QOTD_SELECTION=translation_group`, every language features a member
+/// of the same translation group for the day; otherwise each language draws
+/// independently.
+fn qotd_uses_translation_groups() -> bool {
+    utils::env::load_env_var("QOTD_SELECTION")
+        .map(|value| value == "translation_group")
+        .unwrap_or(false)
+}
 
 async fn update_qotd(db_conn: &DatabaseConnection, redis: &redis::Client) -> Result<(), AppError> {
-    let tasks: Vec<_> = utils::constants::Language::variants()
+    let min_grade = attribution::qotd_min_attribution();
+    let weighted = ratings::qotd_uses_weighting();
+    let qotd_languages: Vec<String> = pg::languages::fetch_qotd_languages(db_conn)
+        .await?
+        .into_iter()
+        .map(|lang| lang.code)
+        .collect();
+
+    let grouped = match qotd_uses_translation_groups() {
+        true => {
+            let languages: Vec<&str> = qotd_languages.iter().map(String::as_str).collect();
+            pg::translations::pick_random_complete_group(db_conn, &languages, &min_grade)
+                .await?
+                .unwrap_or_default()
+        }
+        false => HashMap::new(),
+    };
+
+    let tasks: Vec<_> = qotd_languages
         .into_iter()
         .map(|lang| {
             let db_conn = db_conn.clone();
             let redis = redis.clone();
+            let min_grade = min_grade.clone();
+            let grouped_id = grouped.get(&lang).copied();
             async move {
-                let response = pg::fetch_random_quote_by_language(&db_conn, lang.as_str()).await?;
+                let response = match grouped_id {
+                    Some(quote_id) => pg::fetch_quote_by_id(&db_conn, quote_id).await?,
+                    None if weighted => {
+                        ratings::weighted_random_quote(&db_conn, &lang, Some(&min_grade)).await?
+                    }
+                    None => {
+                        pg::fetch_random_quote_by_language(&db_conn, &lang, Some(&min_grade))
+                            .await?
+                    }
+                };
                 pg::update_qotd_in_db(&db_conn, &response).await?;
                 rds::update_qotd_into_redis(&redis, &response).await?;
                 Ok::<(), AppError>(())
@@ -26,7 +72,19 @@ async fn update_qotd(db_conn: &DatabaseConnection, redis: &redis::Client) -> Res
         })
         .collect();
 
-    future::try_join_all(tasks).await.map(|_| ())
+    // A language without eligible quotes must not hold back the others.
+    let mut first_error = None;
+    for result in future::join_all(tasks).await {
+        if let Err(e) = result {
+            error!("Failed to roll over QOTD: {:?}", e);
+            first_error.get_or_insert(e);
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
 }
 
 fn should_update_cache(last_timestamp: i64) -> bool {
@@ -122,6 +180,104 @@ pub async fn get_qotd_by_language(
     }
 }
 
+/// Serves a random quote from the first language in `chain` that has one,
+/// biased towards popular quotes when `weighted` is set.
+pub async fn random_quote_with_fallback(
+    db_conn: &DatabaseConnection,
+    chain: &[languages::Model],
+    min_grade: Option<&AttributionStatus>,
+    weighted: bool,
+) -> Result<ResponseQuote, AppError> {
+    for lang in chain {
+        let quote = match weighted {
+            true => ratings::weighted_random_quote(db_conn, &lang.code, min_grade).await,
+            false => pg::fetch_random_quote_by_language(db_conn, &lang.code, min_grade).await,
+        };
+        match quote {
+            Ok(quote) => return Ok(quote),
+            Err(AppError::NotFound(msg)) => {
+                info!("No quote for '{}', trying fallback: {}", lang.code, msg);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(AppError::NotFound(
+        "No quotes found in the database.".to_string(),
+    ))
+}
+
+/// Serves the quote of the day from the first QOTD-enabled language in
+/// `chain` that has one.
+pub async fn qotd_with_fallback(
+    db_conn: &DatabaseConnection,
+    redis_client: &redis::Client,
+    chain: &[languages::Model],
+) -> Result<ResponseQuote, AppError> {
+    for lang in chain.iter().filter(|lang| lang.qotd_enabled) {
+        match get_qotd_by_language(db_conn, redis_client, &lang.code).await {
+            Ok(quote) => return Ok(quote),
+            Err(AppError::NotFound(msg)) => {
+                info!("No QOTD for '{}', trying fallback: {}", lang.code, msg);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(AppError::NotFound(
+        "No quote of the day found for this language".to_string(),
+    ))
+}
+
+fn strip_quote_diacritics(quote: &mut ResponseQuote) {
+    quote.content = arabic::strip_diacritics(&quote.content);
+    quote.author = arabic::strip_diacritics(&quote.author);
+}
+
+fn parse_diacritics(value: Option<&str>) -> Result<bool, AppError> {
+    match value.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("keep") => Ok(false),
+        Some("strip") => Ok(true),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Unsupported diacritics option '{}', expected keep or strip",
+            other
+        ))),
+    }
+}
+
+/// Applies the display options shared by the quote endpoints: script
+/// conversion for Urdu, diacritics stripping for Arabic script and
+/// side-by-side translations.
+pub async fn build_display_payload(
+    db_conn: &DatabaseConnection,
+    mut quote: ResponseQuote,
+    display: &DisplayQuery,
+) -> Result<QuotePayload, AppError> {
+    let strip_diacritics = parse_diacritics(display.diacritics.as_deref())?;
+
+    if let Some(script) = display.script.as_deref() {
+        transliteration::apply_script(&mut quote, UrduScript::parse(script)?)?;
+    }
+
+    let mut payload =
+        translations::build_quote_payload(db_conn, quote, display.with.as_deref()).await?;
+
+    if strip_diacritics {
+        match &mut payload {
+            QuotePayload::Single(quote) => strip_quote_diacritics(quote),
+            QuotePayload::Bilingual(bilingual) => {
+                strip_quote_diacritics(&mut bilingual.quote);
+                bilingual
+                    .translations
+                    .iter_mut()
+                    .for_each(strip_quote_diacritics);
+            }
+        }
+    }
+
+    Ok(payload)
+}
+
 pub async fn get_qotd_from_db_and_create_redis_cache(
     db_conn: &DatabaseConnection,
     redis_conn: &mut redis::aio::Connection,
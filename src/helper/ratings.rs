@@ -0,0 +1,247 @@
+use crate::db::queries::pg;
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::models::data::{PageQuery, PagedResponse, PopularQuote, RatingSummary, ResponseQuote};
+use crate::models::errors::AppError;
+use crate::utils;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Unrated quotes score as if they had `PRIOR_WEIGHT` ratings of
+/// `PRIOR_MEAN`, so a single 5 does not outrank a well-rated classic.
+const PRIOR_MEAN: f64 = 3.0;
+const PRIOR_WEIGHT: f64 = 5.0;
+
+/// Bayesian average of a quote's ratings. Doubles as its sampling weight in
+/// weighted selection, so a quote scored 5 is drawn about five times as often
+/// as one scored 1.
+pub fn score(ratings: i64, total: i64) -> f64 {
+    (PRIOR_WEIGHT * PRIOR_MEAN + total as f64) / (PRIOR_WEIGHT + ratings as f64)
+}
+
+/// `QOTD_WEIGHTED=true` biases the daily rollover towards popular quotes.
+pub fn qotd_uses_weighting() -> bool {
+    utils::env::load_env_var("QOTD_WEIGHTED")
+        .map(|value| value == "true")
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl Period {
+    pub fn since(&self) -> Option<DateTime<Utc>> {
+        let days = match self {
+            Period::Day => 1,
+            Period::Week => 7,
+            Period::Month => 30,
+            Period::Year => 365,
+            Period::All => return None,
+        };
+        Some(Utc::now() - Duration::days(days))
+    }
+}
+
+impl FromStr for Period {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "day" => Ok(Period::Day),
+            "week" => Ok(Period::Week),
+            "month" => Ok(Period::Month),
+            "year" => Ok(Period::Year),
+            "all" => Ok(Period::All),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported period '{}', expected one of day, week, month, year, all",
+                other
+            ))),
+        }
+    }
+}
+
+async fn rating_summary(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+) -> Result<RatingSummary, AppError> {
+    let (ratings, total) = pg::ratings::fetch_quote_stats(db, quote_id)
+        .await?
+        .map(|(_, ratings, total)| (ratings, total))
+        .unwrap_or_default();
+
+    Ok(RatingSummary {
+        quote_id,
+        ratings,
+        average: (ratings > 0).then(|| total as f64 / ratings as f64),
+        score: score(ratings, total),
+        mine: pg::ratings::fetch_rating(db, key_id, quote_id).await?,
+    })
+}
+
+pub async fn rate_quote(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+    value: i16,
+) -> Result<RatingSummary, AppError> {
+    if !(1..=5).contains(&value) {
+        return Err(AppError::BadRequest(format!(
+            "Rating {} must be between 1 and 5",
+            value
+        )));
+    }
+    pg::admin_quotes::ensure_quotes_exist(db, &[quote_id]).await?;
+
+    pg::ratings::upsert_rating(db, key_id, quote_id, value).await?;
+    rating_summary(db, key_id, quote_id).await
+}
+
+pub async fn remove_rating(
+    db: &DatabaseConnection,
+    key_id: &str,
+    quote_id: i32,
+) -> Result<RatingSummary, AppError> {
+    pg::ratings::delete_rating(db, key_id, quote_id).await?;
+    rating_summary(db, key_id, quote_id).await
+}
+
+pub async fn popular_quotes(
+    db: &DatabaseConnection,
+    language: Option<&str>,
+    period: Period,
+    page: &PageQuery,
+) -> Result<PagedResponse<PopularQuote>, AppError> {
+    let (stats, total) =
+        pg::ratings::fetch_popular(db, language, period.since(), PRIOR_MEAN, PRIOR_WEIGHT, page)
+            .await?;
+
+    let ids: Vec<i32> = stats.iter().map(|(quote_id, _, _)| *quote_id).collect();
+    let mut quotes: HashMap<i32, ResponseQuote> = pg::fetch_quotes_by_ids(db, &ids)
+        .await?
+        .into_iter()
+        .map(|quote| (quote.id, quote))
+        .collect();
+
+    let items = stats
+        .into_iter()
+        .filter_map(|(quote_id, ratings, sum)| {
+            quotes.remove(&quote_id).map(|quote| PopularQuote {
+                quote,
+                ratings,
+                average: sum as f64 / ratings as f64,
+                score: score(ratings, sum),
+            })
+        })
+        .collect();
+
+    Ok(PagedResponse {
+        items,
+        page: page.page(),
+        per_page: page.per_page(),
+        total,
+    })
+}
+
+/// Draws one of `candidates` with probability proportional to its score, or
+/// none when there is nothing to draw from. Scores that cannot be used as
+/// weights are an internal error rather than an empty result.
+pub async fn pick_weighted(
+    db: &DatabaseConnection,
+    language: &str,
+    candidates: &[i32],
+) -> Result<Option<i32>, AppError> {
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let stats = pg::ratings::fetch_language_stats(db, language).await?;
+    let weights = candidates.iter().map(|quote_id| {
+        let (ratings, total) = stats.get(quote_id).copied().unwrap_or_default();
+        score(ratings, total)
+    });
+    let index = WeightedIndex::new(weights).map_err(|e| {
+        AppError::Internal(format!("Cannot weight quotes in '{}': {}", language, e))
+    })?;
+
+    Ok(Some(candidates[index.sample(&mut rand::thread_rng())]))
+}
+
+/// Like `pg::fetch_random_quote_by_language`, biased towards popular quotes.
+pub async fn weighted_random_quote(
+    db: &DatabaseConnection,
+    language: &str,
+    min_grade: Option<&AttributionStatus>,
+) -> Result<ResponseQuote, AppError> {
+    let candidates = pg::fetch_ids_by_language(db, language, min_grade).await?;
+    match pick_weighted(db, language, &candidates).await? {
+        Some(quote_id) => pg::fetch_quote_by_id(db, quote_id).await,
+        None => Err(AppError::NotFound(
+            "No quotes found in the database.".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_starts_at_the_prior_mean() {
+        assert_eq!(score(0, 0), PRIOR_MEAN);
+    }
+
+    #[test]
+    fn score_moves_towards_the_average_with_more_ratings() {
+        let one_five = score(1, 5);
+        let many_fives = score(100, 500);
+        assert!(one_five > PRIOR_MEAN && one_five < 4.0);
+        assert!(many_fives > 4.8 && many_fives < 5.0);
+        assert!(score(100, 100) < score(1, 1));
+    }
+
+    #[test]
+    fn score_stays_within_the_rating_range() {
+        for ratings in 0..50 {
+            for total in ratings..=ratings * 5 {
+                let score = score(ratings, total);
+                assert!((1.0..=5.0).contains(&score), "{} {}", ratings, total);
+            }
+        }
+    }
+
+    #[test]
+    fn period_parses_case_insensitively() {
+        assert_eq!("day".parse::<Period>().unwrap(), Period::Day);
+        assert_eq!("Week".parse::<Period>().unwrap(), Period::Week);
+        assert_eq!("MONTH".parse::<Period>().unwrap(), Period::Month);
+        assert_eq!("year".parse::<Period>().unwrap(), Period::Year);
+        assert_eq!("all".parse::<Period>().unwrap(), Period::All);
+    }
+
+    #[test]
+    fn period_rejects_unknown_values() {
+        assert!(matches!(
+            "fortnight".parse::<Period>(),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!("".parse::<Period>().is_err());
+    }
+
+    #[test]
+    fn period_all_has_no_start() {
+        assert!(Period::All.since().is_none());
+        let since = Period::Week.since().unwrap();
+        assert_eq!((Utc::now() - since).num_days(), 7);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::db::queries::{pg, rds};
+use crate::entities::quote_reports;
+use crate::helper::admin_quotes::{self, optional, WriteWarnings};
+use crate::models::data::{
+    PageQuery, PagedResponse, ReportDismissRequest, ReportQuery, ReportRequest, ReportResolution,
+    ReportResolveRequest, ReportStatus, ResponseReport,
+};
+use crate::models::errors::AppError;
+use crate::utils::constants::ReportLimit;
+
+use chrono::Utc;
+use log::error;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{DatabaseConnection, SqlErr, TransactionTrait};
+
+fn to_response_report(report: quote_reports::Model) -> ResponseReport {
+    ResponseReport {
+        id: report.id,
+        quote_id: report.quote_id,
+        reason: report.reason,
+        details: report.details,
+        reported_by: report.reported_by,
+        status: report.status,
+        resolution: report.resolution,
+        revision: report.revision,
+        resolved_by: report.resolved_by,
+        resolved_at: report
+            .resolved_at
+            .map(|resolved_at| resolved_at.with_timezone(&Utc)),
+        created_at: report.created_at.with_timezone(&Utc),
+    }
+}
+
+/// Limits each key to `ReportLimit::PerWindow` reports per window. An
+/// unreachable Redis is logged and lets the report through.
+async fn check_report_limit(redis_client: &redis::Client, key_id: &str) -> Result<(), AppError> {
+    let window = ReportLimit::WindowSeconds.as_u64();
+    match rds::increment_report_count(redis_client, key_id, window).await {
+        Ok(count) if count > ReportLimit::PerWindow.as_u64() => Err(AppError::ApiKeyError(
+            "Report limit exceeded. Please try again later.".to_string(),
+        )),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to count reports for key '{}': {}", key_id, e);
+            Ok(())
+        }
+    }
+}
+
+pub async fn report_quote(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    reported_by: &str,
+    quote_id: i32,
+    request: &ReportRequest,
+) -> Result<ResponseReport, AppError> {
+    let already_open = || {
+        AppError::BadRequest(format!(
+            "You already have an open report on quote {}",
+            quote_id
+        ))
+    };
+
+    pg::admin_quotes::ensure_quotes_exist(db, &[quote_id]).await?;
+    if pg::reports::has_open_report(db, quote_id, reported_by).await? {
+        return Err(already_open());
+    }
+    check_report_limit(redis_client, reported_by).await?;
+
+    // A concurrent report can still get in between the check and the insert;
+    // `quote_reports_open_idx` turns that into a unique violation.
+    let report = pg::reports::insert_report(
+        db,
+        quote_reports::ActiveModel {
+            quote_id: Set(quote_id),
+            reason: Set(request.reason.as_str().to_string()),
+            details: Set(optional(request.details.as_deref())),
+            reported_by: Set(reported_by.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| match e {
+        AppError::DatabaseError(ref db_err)
+            if matches!(db_err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+        {
+            already_open()
+        }
+        e => e,
+    })?;
+
+    Ok(to_response_report(report))
+}
+
+pub async fn list_reports(
+    db: &DatabaseConnection,
+    query: &ReportQuery,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseReport>, AppError> {
+    let reports = pg::reports::fetch_reports(db, query, page).await?;
+    Ok(PagedResponse {
+        items: reports.items.into_iter().map(to_response_report).collect(),
+        page: reports.page,
+        per_page: reports.per_page,
+        total: reports.total,
+    })
+}
+
+async fn fetch_open(
+    db: &DatabaseConnection,
+    report_id: i32,
+) -> Result<quote_reports::Model, AppError> {
+    let report = pg::reports::fetch_report(db, report_id).await?;
+    match report.status == ReportStatus::Open.as_str() {
+        true => Ok(report),
+        false => Err(AppError::BadRequest(format!(
+            "Report {} is already {}",
+            report_id, report.status
+        ))),
+    }
+}
+
+/// Resolves a report. With an `edit`, the quote is updated through the admin
+/// quote path and the resulting revision is kept on the report. The report is
+/// closed first, in the edit's transaction, so a second admin resolving it at
+/// the same time fails before touching the quote.
+pub async fn resolve_report(
+    db: &DatabaseConnection,
+    redis_client: &redis::Client,
+    report_id: i32,
+    request: &ReportResolveRequest,
+    resolved_by: Option<&str>,
+) -> Result<(ReportResolution, WriteWarnings), AppError> {
+    let report = fetch_open(db, report_id).await?;
+    let prepared = match &request.edit {
+        Some(edit) => Some(admin_quotes::prepare_update(db, report.quote_id, edit).await?),
+        None => None,
+    };
+
+    let txn = db.begin().await?;
+    let mut report = pg::reports::close_report(
+        &txn,
+        report_id,
+        ReportStatus::Resolved,
+        optional(request.note.as_deref()),
+        None,
+        resolved_by,
+    )
+    .await?;
+
+    let mut edited = None;
+    let mut lint = Vec::new();
+    if let (Some((quote, issues)), Some(edit)) = (prepared, &request.edit) {
+        let (quote, languages) =
+            admin_quotes::apply_update(&txn, quote, edit.category_ids.as_deref(), resolved_by)
+                .await?;
+        let revision = pg::revisions::fetch_latest_revision(&txn, quote.id).await?;
+        report = pg::reports::set_report_revision(&txn, report_id, revision).await?;
+        edited = Some((quote, languages));
+        lint = issues;
+    }
+    txn.commit().await?;
+
+    let quote = match edited {
+        Some((quote, languages)) => {
+            for lang in languages {
+                admin_quotes::invalidate_cached_qotd(redis_client, &lang).await;
+            }
+            Some(pg::to_response_quote(db, quote).await?)
+        }
+        None => None,
+    };
+
+    Ok((
+        ReportResolution {
+            report: to_response_report(report),
+            quote,
+        },
+        WriteWarnings {
+            lint,
+            ..Default::default()
+        },
+    ))
+}
+
+pub async fn dismiss_report(
+    db: &DatabaseConnection,
+    report_id: i32,
+    request: &ReportDismissRequest,
+    resolved_by: Option<&str>,
+) -> Result<ResponseReport, AppError> {
+    fetch_open(db, report_id).await?;
+
+    let report = pg::reports::close_report(
+        db,
+        report_id,
+        ReportStatus::Dismissed,
+        optional(request.note.as_deref()),
+        None,
+        resolved_by,
+    )
+    .await?;
+    Ok(to_response_report(report))
+}
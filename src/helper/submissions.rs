@@ -0,0 +1,236 @@
+use crate::db::queries::pg;
+use crate::entities::{quote_submissions, quotes};
+use crate::helper::admin_quotes::{self, optional, required, WriteWarnings};
+use crate::helper::languages;
+use crate::models::data::{
+    PageQuery, PagedResponse, QuoteRequest, ResponseSubmission, SubmissionApproval,
+    SubmissionQuery, SubmissionRequest, SubmissionStatus, SubmissionUpdateRequest,
+};
+use crate::models::errors::AppError;
+
+use chrono::Utc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveEnum, ColumnTrait, DatabaseConnection, IntoActiveModel, QueryFilter, TransactionTrait,
+};
+
+fn to_response_submission(submission: quote_submissions::Model) -> ResponseSubmission {
+    ResponseSubmission {
+        id: submission.id,
+        status: submission.status,
+        content: submission.content,
+        author: submission.author,
+        reference: submission.reference,
+        language: submission.language,
+        category_ids: submission.category_ids,
+        notes: submission.notes,
+        rejection_reason: submission.rejection_reason,
+        quote_id: submission.quote_id,
+        translation_of: submission.translation_of,
+        submitted_by: submission.submitted_by,
+        reviewed_by: submission.reviewed_by,
+        reviewed_at: submission
+            .reviewed_at
+            .map(|reviewed_at| reviewed_at.with_timezone(&Utc)),
+        created_at: submission.created_at.with_timezone(&Utc),
+        updated_at: submission.updated_at.with_timezone(&Utc),
+    }
+}
+
+fn to_response_page(
+    page: PagedResponse<quote_submissions::Model>,
+) -> PagedResponse<ResponseSubmission> {
+    PagedResponse {
+        items: page.items.into_iter().map(to_response_submission).collect(),
+        page: page.page,
+        per_page: page.per_page,
+        total: page.total,
+    }
+}
+
+async fn fetch_pending(
+    db: &DatabaseConnection,
+    submission_id: i32,
+) -> Result<quote_submissions::Model, AppError> {
+    let submission = pg::submissions::fetch_submission(db, submission_id).await?;
+    match submission.status == SubmissionStatus::Pending.as_str() {
+        true => Ok(submission),
+        false => Err(AppError::BadRequest(format!(
+            "Submission {} is already {}",
+            submission_id, submission.status
+        ))),
+    }
+}
+
+pub async fn submit(
+    db: &DatabaseConnection,
+    submitted_by: &str,
+    request: &SubmissionRequest,
+) -> Result<ResponseSubmission, AppError> {
+    let content = required("content", &request.content)?;
+    let author = required("author", &request.author)?;
+    let language = languages::validate_language(db, &request.language).await?;
+    pg::admin_quotes::ensure_categories_exist(db, &request.category_ids).await?;
+
+    let submission = pg::submissions::insert_submission(
+        db,
+        quote_submissions::ActiveModel {
+            submitted_by: Set(submitted_by.to_string()),
+            content: Set(content),
+            author: Set(author),
+            reference: Set(optional(request.reference.as_deref())),
+            language: Set(language),
+            category_ids: Set(request.category_ids.clone()),
+            notes: Set(optional(request.notes.as_deref())),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(to_response_submission(submission))
+}
+
+pub async fn list_submissions(
+    db: &DatabaseConnection,
+    submitted_by: Option<&str>,
+    query: &SubmissionQuery,
+    page: &PageQuery,
+) -> Result<PagedResponse<ResponseSubmission>, AppError> {
+    let submissions =
+        pg::submissions::fetch_submissions(db, submitted_by, query.status, page).await?;
+    Ok(to_response_page(submissions))
+}
+
+pub async fn update_submission(
+    db: &DatabaseConnection,
+    submission_id: i32,
+    request: &SubmissionUpdateRequest,
+) -> Result<ResponseSubmission, AppError> {
+    let mut submission = fetch_pending(db, submission_id).await?.into_active_model();
+
+    if let Some(content) = &request.content {
+        submission.content = Set(required("content", content)?);
+    }
+    if let Some(author) = &request.author {
+        submission.author = Set(required("author", author)?);
+    }
+    if request.reference.is_some() {
+        submission.reference = Set(optional(request.reference.as_deref()));
+    }
+    if let Some(language) = &request.language {
+        submission.language = Set(languages::validate_language(db, language).await?);
+    }
+    if let Some(category_ids) = &request.category_ids {
+        pg::admin_quotes::ensure_categories_exist(db, category_ids).await?;
+        submission.category_ids = Set(category_ids.clone());
+    }
+    if request.notes.is_some() {
+        submission.notes = Set(optional(request.notes.as_deref()));
+    }
+
+    let submission = pg::submissions::update_submission(db, submission).await?;
+    Ok(to_response_submission(submission))
+}
+
+/// Creates the quote through the admin create path, so validation and dedup
+/// apply as for any admin write, then marks the submission approved. The
+/// submission is claimed first, in the quote's transaction, so two admins
+/// approving at once cannot both create it. `reference` fills in for a
+/// submission sent without one. A translation of a live quote takes over its
+/// author and grading and joins its translation group. Returns the write's
+/// non-blocking findings.
+pub async fn approve_submission(
+    db: &DatabaseConnection,
+    submission_id: i32,
+    reference: Option<&str>,
+    reviewed_by: Option<&str>,
+) -> Result<(SubmissionApproval, WriteWarnings), AppError> {
+    let submission = fetch_pending(db, submission_id).await?;
+    let reference = optional(reference)
+        .or(submission.reference)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Submission {} has no reference; pass one to approve it",
+                submission_id
+            ))
+        })?;
+    let original = match submission.translation_of {
+        Some(original_id) => {
+            pg::find_live_quotes()
+                .filter(quotes::Column::Id.eq(original_id))
+                .one(db)
+                .await?
+        }
+        None => None,
+    };
+    let request = QuoteRequest {
+        content: submission.content,
+        author: submission.author,
+        author_id: original.as_ref().and_then(|original| original.author_id),
+        reference,
+        language: submission.language,
+        attribution_status: original
+            .as_ref()
+            .map(|original| original.attribution_status.to_value()),
+        grading_notes: original
+            .as_ref()
+            .and_then(|original| original.grading_notes.clone()),
+        graded_by: original
+            .as_ref()
+            .and_then(|original| original.graded_by.clone()),
+        category_ids: pg::admin_quotes::fetch_existing_category_ids(db, &submission.category_ids)
+            .await?,
+    };
+    let (quote, warnings) = admin_quotes::prepare_quote(db, &request).await?;
+
+    let txn = db.begin().await?;
+    pg::submissions::mark_reviewed(
+        &txn,
+        submission_id,
+        SubmissionStatus::Approved,
+        None,
+        None,
+        reviewed_by,
+    )
+    .await?;
+    let mut quote =
+        pg::admin_quotes::insert_quote(&txn, quote, &request.category_ids, reviewed_by).await?;
+    if let Some(original) = &original {
+        if let Some(group_id) =
+            pg::translations::join_translation_group(&txn, original.id, quote.id).await?
+        {
+            quote.translation_group_id = Some(group_id);
+        }
+    }
+    let submission = pg::submissions::set_submission_quote(&txn, submission_id, quote.id).await?;
+    txn.commit().await?;
+
+    Ok((
+        SubmissionApproval {
+            submission: to_response_submission(submission),
+            quote: pg::to_response_quote(db, quote).await?,
+        },
+        warnings,
+    ))
+}
+
+pub async fn reject_submission(
+    db: &DatabaseConnection,
+    submission_id: i32,
+    reason: &str,
+    reviewed_by: Option<&str>,
+) -> Result<ResponseSubmission, AppError> {
+    let reason = required("reason", reason)?;
+    fetch_pending(db, submission_id).await?;
+
+    let submission = pg::submissions::mark_reviewed(
+        db,
+        submission_id,
+        SubmissionStatus::Rejected,
+        Some(reason),
+        None,
+        reviewed_by,
+    )
+    .await?;
+    Ok(to_response_submission(submission))
+}
@@ -0,0 +1,58 @@
+use crate::db::queries::pg;
+use crate::models::data::{BilingualResponse, QuotePayload, ResponseQuote};
+use crate::models::errors::AppError;
+
+use sea_orm::DatabaseConnection;
+
+fn parse_with(with: &str) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    for lang in with
+        .split(',')
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty())
+    {
+        if !languages.iter().any(|existing| existing == lang) {
+            languages.push(lang.to_string());
+        }
+    }
+    languages
+}
+
+/// Wraps `quote` together with its translations in the languages listed in
+/// `with` (comma separated), or returns it unchanged when `with` is absent.
+pub async fn build_quote_payload(
+    db: &DatabaseConnection,
+    quote: ResponseQuote,
+    with: Option<&str>,
+) -> Result<QuotePayload, AppError> {
+    let Some(with) = with else {
+        return Ok(QuotePayload::Single(quote));
+    };
+
+    let requested: Vec<String> = parse_with(with)
+        .into_iter()
+        .filter(|lang| *lang != quote.language)
+        .collect();
+
+    let mut available = pg::translations::fetch_translations(db, quote.id)
+        .await?
+        .translations;
+
+    let mut translations = Vec::with_capacity(requested.len());
+    let mut missing = Vec::new();
+    for lang in requested {
+        match available
+            .iter()
+            .position(|candidate| candidate.language == lang)
+        {
+            Some(index) => translations.push(available.swap_remove(index)),
+            None => missing.push(lang),
+        }
+    }
+
+    Ok(QuotePayload::Bilingual(BilingualResponse {
+        quote,
+        translations,
+        missing,
+    }))
+}
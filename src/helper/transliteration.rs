@@ -0,0 +1,324 @@
+use crate::models::data::ResponseQuote;
+use crate::models::errors::AppError;
+
+/// `submitted_by` of the submissions proposed by the Urdu script seeding tool.
+pub const SEED_SUBMITTER: &str = "transliteration";
+
+/// Target script for Urdu text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrduScript {
+    Arab,
+    Latn,
+}
+
+impl UrduScript {
+    pub fn parse(value: &str) -> Result<UrduScript, AppError> {
+        match value.to_ascii_lowercase().as_str() {
+            "arab" => Ok(UrduScript::Arab),
+            "latn" => Ok(UrduScript::Latn),
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported script '{}', expected arab or latn",
+                other
+            ))),
+        }
+    }
+
+    pub fn language_code(&self) -> &'static str {
+        match self {
+            UrduScript::Arab => "ur-Arab",
+            UrduScript::Latn => "ur-Latn",
+        }
+    }
+
+    pub fn direction(&self) -> &'static str {
+        match self {
+            UrduScript::Arab => "rtl",
+            UrduScript::Latn => "ltr",
+        }
+    }
+}
+
+/// Roman Urdu clusters, longest first, with their position-dependent forms:
+/// (latin, initial, medial, final).
+const ROMAN_CLUSTERS: &[(&str, &str, &str, &str)] = &[
+    ("kh", "خ", "خ", "خ"),
+    ("gh", "غ", "غ", "غ"),
+    ("sh", "ش", "ش", "ش"),
+    ("ch", "چ", "چ", "چ"),
+    ("zh", "ژ", "ژ", "ژ"),
+    ("th", "تھ", "تھ", "تھ"),
+    ("ph", "پھ", "پھ", "پھ"),
+    ("bh", "بھ", "بھ", "بھ"),
+    ("dh", "دھ", "دھ", "دھ"),
+    ("jh", "جھ", "جھ", "جھ"),
+    ("aa", "آ", "ا", "ا"),
+    ("ee", "ای", "ی", "ی"),
+    ("oo", "او", "و", "و"),
+    ("ai", "اے", "ے", "ے"),
+    ("ay", "اے", "ے", "ے"),
+    ("au", "او", "و", "و"),
+    ("ou", "او", "و", "و"),
+    ("a", "ا", "", "ا"),
+    ("i", "ا", "", "ی"),
+    ("u", "ا", "", "و"),
+    ("e", "ا", "ی", "ے"),
+    ("o", "او", "و", "و"),
+    ("b", "ب", "ب", "ب"),
+    ("c", "ک", "ک", "ک"),
+    ("d", "د", "د", "د"),
+    ("f", "ف", "ف", "ف"),
+    ("g", "گ", "گ", "گ"),
+    ("h", "ہ", "ہ", "ہ"),
+    ("j", "ج", "ج", "ج"),
+    ("k", "ک", "ک", "ک"),
+    ("l", "ل", "ل", "ل"),
+    ("m", "م", "م", "م"),
+    ("n", "ن", "ن", "ن"),
+    ("p", "پ", "پ", "پ"),
+    ("q", "ق", "ق", "ق"),
+    ("r", "ر", "ر", "ر"),
+    ("s", "س", "س", "س"),
+    ("t", "ت", "ت", "ت"),
+    ("v", "و", "و", "و"),
+    ("w", "و", "و", "و"),
+    ("x", "کس", "کس", "کس"),
+    ("y", "ی", "ی", "ی"),
+    ("z", "ز", "ز", "ز"),
+];
+
+fn roman_word_to_urdu(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let mut output = String::new();
+    let mut index = 0;
+
+    while index < lower.len() {
+        let rest = &lower[index..];
+        match ROMAN_CLUSTERS
+            .iter()
+            .find(|(latin, ..)| rest.starts_with(latin))
+        {
+            Some((latin, initial, medial, last)) => {
+                let form = if index == 0 {
+                    initial
+                } else if index + latin.len() == lower.len() {
+                    last
+                } else {
+                    medial
+                };
+                output.push_str(form);
+                index += latin.len();
+            }
+            None => {
+                let c = rest.chars().next().unwrap_or_default();
+                output.push(c);
+                index += c.len_utf8();
+            }
+        }
+    }
+
+    output
+}
+
+fn urdu_punctuation(c: char) -> char {
+    match c {
+        '?' => '؟',
+        ',' => '،',
+        ';' => '؛',
+        '.' => '۔',
+        other => other,
+    }
+}
+
+/// Rule-based Roman Urdu to Urdu script conversion. Roman Urdu spelling is
+/// ambiguous ("kh" is both خ and the aspirated کھ, "a" both a short vowel and
+/// ا), so the output is for display and for review, never stored unchecked.
+pub fn roman_to_urdu(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut word = String::new();
+
+    for c in text.chars() {
+        if c.is_ascii_alphabetic() {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            output.push_str(&roman_word_to_urdu(&word));
+            word.clear();
+        }
+        output.push(urdu_punctuation(c));
+    }
+    if !word.is_empty() {
+        output.push_str(&roman_word_to_urdu(&word));
+    }
+
+    output
+}
+
+fn urdu_char_to_roman(c: char, initial: bool, last: bool) -> Option<&'static str> {
+    let roman = match c {
+        'ا' | 'أ' | 'إ' => "a",
+        'آ' => "aa",
+        'ب' => "b",
+        'پ' => "p",
+        'ت' | 'ٹ' | 'ط' | 'ة' => "t",
+        'ث' | 'س' | 'ص' => "s",
+        'ج' => "j",
+        'چ' => "ch",
+        'ح' => "h",
+        'خ' => "kh",
+        'د' | 'ڈ' => "d",
+        'ذ' | 'ز' | 'ض' | 'ظ' => "z",
+        'ر' | 'ڑ' => "r",
+        'ژ' => "zh",
+        'ش' => "sh",
+        'ع' => "",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ک' | 'ك' => "k",
+        'گ' => "g",
+        'ل' => "l",
+        'م' => "m",
+        'ن' | 'ں' => "n",
+        'و' | 'ؤ' if initial => "w",
+        'و' | 'ؤ' => "o",
+        'ہ' | 'ه' | 'ۂ' if last => "a",
+        'ہ' | 'ه' | 'ۂ' | 'ھ' => "h",
+        'ی' | 'ي' | 'ى' if initial => "y",
+        'ی' | 'ي' | 'ى' => "i",
+        'ے' | 'ۓ' => "e",
+        'ء' | 'ئ' => "",
+        '\u{064E}' => "a",
+        '\u{0650}' => "i",
+        '\u{064F}' => "u",
+        '\u{064B}'..='\u{0652}' | '\u{0670}' => "",
+        '؟' => "?",
+        '،' => ",",
+        '؛' => ";",
+        '۔' => ".",
+        _ => return None,
+    };
+    Some(roman)
+}
+
+/// Rule-based Urdu script to Roman Urdu conversion. Short vowels are only
+/// written when the text carries harakat, so unvocalised words come out as
+/// consonant skeletons; the output is meant for display.
+pub fn urdu_to_roman(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+
+    for (index, c) in chars.iter().enumerate() {
+        let initial = index == 0 || !is_urdu_letter(chars[index - 1]);
+        let last = index + 1 == chars.len() || !is_urdu_letter(chars[index + 1]);
+        match urdu_char_to_roman(*c, initial, last) {
+            Some(roman) => output.push_str(roman),
+            None => output.push(*c),
+        }
+    }
+
+    output
+}
+
+fn is_urdu_letter(c: char) -> bool {
+    ('\u{0600}'..='\u{06FF}').contains(&c) && !matches!(c, '؟' | '،' | '؛' | '۔')
+}
+
+/// Converts an Urdu quote into the requested script. Quotes in other
+/// languages are rejected so clients notice a misplaced `script` parameter.
+pub fn apply_script(quote: &mut ResponseQuote, script: UrduScript) -> Result<(), AppError> {
+    let converted = match (quote.language.as_str(), script) {
+        ("ur-Latn", UrduScript::Arab) => roman_to_urdu(&quote.content),
+        ("ur-Arab", UrduScript::Latn) => urdu_to_roman(&quote.content),
+        ("ur-Latn", UrduScript::Latn) | ("ur-Arab", UrduScript::Arab) => return Ok(()),
+        (other, _) => {
+            return Err(AppError::BadRequest(format!(
+                "The script parameter only applies to Urdu quotes, not '{}'",
+                other
+            )))
+        }
+    };
+
+    quote.content = converted;
+    quote.language = script.language_code().to_string();
+    quote.direction = script.direction().to_string();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urdu_quote(language: &str, content: &str) -> ResponseQuote {
+        ResponseQuote {
+            content: content.to_string(),
+            language: language.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn roman_to_urdu_uses_positional_forms() {
+        assert_eq!(roman_to_urdu("dil"), "دل");
+        assert_eq!(roman_to_urdu("aap"), "آپ");
+        assert_eq!(roman_to_urdu("Kya"), "کیا");
+    }
+
+    #[test]
+    fn roman_to_urdu_prefers_longest_cluster() {
+        assert_eq!(roman_to_urdu("shukr"), "شکر");
+        assert_eq!(roman_to_urdu("dard"), "درد");
+    }
+
+    #[test]
+    fn roman_to_urdu_converts_punctuation_and_keeps_the_rest() {
+        assert_eq!(roman_to_urdu("dil, aap? 7."), "دل، آپ؟ 7۔");
+    }
+
+    #[test]
+    fn urdu_to_roman_reads_short_vowels_from_harakat() {
+        assert_eq!(urdu_to_roman("دِل"), "dil");
+        assert_eq!(urdu_to_roman("وَقت"), "waqt");
+        assert_eq!(urdu_to_roman("شُکر"), "shukr");
+    }
+
+    #[test]
+    fn urdu_to_roman_handles_word_boundaries() {
+        assert_eq!(urdu_to_roman("آپ"), "aap");
+        assert_eq!(urdu_to_roman("وَطَن"), "watan");
+        assert_eq!(urdu_to_roman("دِل۔"), "dil.");
+    }
+
+    #[test]
+    fn urdu_to_roman_keeps_unknown_characters() {
+        assert_eq!(urdu_to_roman("دِل 42 ok"), "dil 42 ok");
+    }
+
+    #[test]
+    fn apply_script_converts_and_relabels() {
+        let mut quote = urdu_quote("ur-Latn", "dil");
+        apply_script(&mut quote, UrduScript::Arab).unwrap();
+        assert_eq!(quote.content, "دل");
+        assert_eq!(quote.language, "ur-Arab");
+        assert_eq!(quote.direction, "rtl");
+    }
+
+    #[test]
+    fn apply_script_leaves_matching_script_alone() {
+        let mut quote = urdu_quote("ur-Arab", "دل");
+        apply_script(&mut quote, UrduScript::Arab).unwrap();
+        assert_eq!(quote.content, "دل");
+    }
+
+    #[test]
+    fn apply_script_rejects_other_languages() {
+        let mut quote = urdu_quote("en", "heart");
+        assert!(apply_script(&mut quote, UrduScript::Latn).is_err());
+    }
+
+    #[test]
+    fn urdu_script_parse_is_case_insensitive() {
+        assert_eq!(UrduScript::parse("ARAB").unwrap(), UrduScript::Arab);
+        assert!(UrduScript::parse("deva").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+mod cli;
 mod db;
 mod entities;
 mod helper;
@@ -18,6 +19,11 @@ async fn main() -> Result<(), AppError> {
     std::env::set_var("RUST_LOG", "debug");
     env_logger::init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(&args).await;
+    }
+
     let db = Arc::new(db::conn::setup_db().await?);
     let redis_client = Arc::new(db::conn::steup_redis().await?);
 
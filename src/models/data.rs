@@ -1,14 +1,24 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::entities::sea_orm_active_enums::AttributionStatus;
+use crate::utils::constants::Pagination;
+
 use redis::Client as RedisClient;
 use sea_orm::DatabaseConnection;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::Arc;
 use unkey::Client as UnkeyClient;
 
 #[derive(Clone)]
 pub struct UnkeyApiId(pub String);
 
+/// Unkey id of the API key that made the request, stored in the request
+/// extensions once the key is verified.
+#[derive(Debug, Clone)]
+pub struct ApiKeyId(pub String);
+
 pub struct AppState {
     pub db: Arc<DatabaseConnection>,
     pub redis_client: Arc<RedisClient>,
@@ -21,12 +31,806 @@ pub struct ResponseQuote {
     pub id: i32,
     pub content: String,
     pub author: String,
+    #[serde(default)]
+    pub author_id: Option<i32>,
     pub reference: String,
+    #[serde(default)]
+    pub references: Vec<ResponseReference>,
     pub language: String,
+    #[serde(default = "default_direction")]
+    pub direction: String,
+    #[serde(default)]
+    pub attribution_status: AttributionStatus,
+    #[serde(default)]
+    pub grading_notes: Option<String>,
+    #[serde(default)]
+    pub graded_by: Option<String>,
+    #[serde(default)]
+    pub translation_group_id: Option<i32>,
     #[serde(default = "default_date")]
     pub date: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct BilingualResponse {
+    pub quote: ResponseQuote,
+    pub translations: Vec<ResponseQuote>,
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum QuotePayload {
+    Single(ResponseQuote),
+    Bilingual(BilingualResponse),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ResponseReference {
+    pub id: i32,
+    pub kind: String,
+    pub title: String,
+    pub volume: Option<String>,
+    pub chapter: Option<String>,
+    pub page: Option<String>,
+    pub collection: Option<String>,
+    pub number: Option<String>,
+    pub edition: Option<String>,
+    pub publisher: Option<String>,
+    pub year: Option<i32>,
+    pub url: Option<String>,
+}
+
+impl fmt::Display for ResponseReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)?;
+        if let Some(collection) = &self.collection {
+            write!(f, ", {}", collection)?;
+        }
+        if let Some(volume) = &self.volume {
+            write!(f, ", Vol. {}", volume)?;
+        }
+        if let Some(chapter) = &self.chapter {
+            write!(f, ", Ch. {}", chapter)?;
+        }
+        if let Some(page) = &self.page {
+            write!(f, ", p. {}", page)?;
+        }
+        if let Some(number) = &self.number {
+            write!(f, ", No. {}", number)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ResponseAuthor {
+    pub id: i32,
+    pub name: String,
+    pub names: BTreeMap<String, String>,
+    pub aliases: Vec<String>,
+    pub era: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub death_date: Option<NaiveDate>,
+    pub bio: Option<String>,
+}
+
+/// Authors whose names share a merge key, for an admin to confirm or ignore.
+#[derive(Debug, Serialize)]
+pub struct AuthorMergeGroup {
+    pub key: String,
+    pub authors: Vec<AuthorMergeMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorMergeMember {
+    pub id: i32,
+    pub name: String,
+    pub quotes: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthorMergeRequest {
+    pub into: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorMergeResponse {
+    pub merged_id: i32,
+    pub into: ResponseAuthor,
+    pub quotes_moved: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseLanguage {
+    pub code: String,
+    pub name: String,
+    pub native_name: String,
+    pub script: String,
+    pub direction: String,
+    pub quote_count: i64,
+    pub has_qotd: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseCategory {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub names: BTreeMap<String, String>,
+    pub parent_id: Option<i32>,
+    pub path: Vec<String>,
+    pub quote_counts: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryRequest {
+    pub name: String,
+    pub slug: Option<String>,
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryUpdateRequest {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub names: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryMergeRequest {
+    pub into: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryMergeResponse {
+    pub merged_id: i32,
+    pub into: ResponseCategory,
+    pub quotes_moved: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkCategoryRequest {
+    pub quote_ids: Vec<i32>,
+    #[serde(default)]
+    pub attach: Vec<i32>,
+    #[serde(default)]
+    pub detach: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkCategoryResponse {
+    pub attached: u64,
+    pub detached: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LanguageQuery {
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    pub since: Option<i64>,
+    pub lang: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// One entry of the change feed. `quote` is absent for deletions. Clients
+/// that never saw the insert of an updated quote should treat the update as
+/// an insert.
+#[derive(Debug, Serialize)]
+pub struct QuoteChange {
+    pub cursor: i64,
+    pub op: ChangeOp,
+    pub quote_id: i32,
+    pub language: String,
+    pub changed_at: DateTime<Utc>,
+    pub quote: Option<ResponseQuote>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangesResponse {
+    pub changes: Vec<QuoteChange>,
+    pub cursor: i64,
+    pub has_more: bool,
+}
+
+/// Offline snapshot of every quote in a language. `cursor` is where the
+/// client should start reading `GET /changes?lang=` afterwards.
+#[derive(Debug, Serialize)]
+pub struct QuoteBundle {
+    pub language: String,
+    pub cursor: i64,
+    pub generated_at: DateTime<Utc>,
+    pub count: usize,
+    pub quotes: Vec<ResponseQuote>,
+}
+
+/// Describes a cached, gzip-compressed `QuoteBundle`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleMeta {
+    pub language: String,
+    pub cursor: i64,
+    pub sha256: String,
+    pub size: usize,
+    pub count: usize,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+impl PageQuery {
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+            .unwrap_or(Pagination::DefaultPerPage.as_u64())
+            .clamp(1, Pagination::MaxPerPage.as_u64())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PagedResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub per_page: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RandomQuoteQuery {
+    pub min_grade: Option<String>,
+    #[serde(default)]
+    pub weighted: bool,
+    #[serde(flatten)]
+    pub display: DisplayQuery,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisplayQuery {
+    pub with: Option<String>,
+    pub script: Option<String>,
+    pub diacritics: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuoteRequest {
+    pub content: String,
+    pub author: String,
+    pub author_id: Option<i32>,
+    pub reference: String,
+    pub language: String,
+    pub attribution_status: Option<String>,
+    pub grading_notes: Option<String>,
+    pub graded_by: Option<String>,
+    #[serde(default)]
+    pub category_ids: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuoteUpdateRequest {
+    pub content: Option<String>,
+    pub author: Option<String>,
+    pub author_id: Option<i32>,
+    pub reference: Option<String>,
+    pub language: Option<String>,
+    pub attribution_status: Option<String>,
+    pub grading_notes: Option<String>,
+    pub graded_by: Option<String>,
+    pub category_ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevisionAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Revert,
+}
+
+impl RevisionAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionAction::Create => "create",
+            RevisionAction::Update => "update",
+            RevisionAction::Delete => "delete",
+            RevisionAction::Restore => "restore",
+            RevisionAction::Revert => "revert",
+        }
+    }
+}
+
+/// A quote's text, author, reference and categories after one admin change.
+#[derive(Debug, Serialize)]
+pub struct ResponseRevision {
+    pub revision: i32,
+    pub action: String,
+    pub content: String,
+    pub author: String,
+    pub author_id: Option<i32>,
+    pub reference: Option<String>,
+    pub category_ids: Vec<i32>,
+    pub changed_by: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Pending => "pending",
+            SubmissionStatus::Approved => "approved",
+            SubmissionStatus::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmissionRequest {
+    pub content: String,
+    pub author: String,
+    pub reference: Option<String>,
+    pub language: String,
+    #[serde(default)]
+    pub category_ids: Vec<i32>,
+    pub notes: Option<String>,
+}
+
+/// Admin edits to a pending submission; absent fields are left unchanged.
+#[derive(Debug, Deserialize)]
+pub struct SubmissionUpdateRequest {
+    pub content: Option<String>,
+    pub author: Option<String>,
+    pub reference: Option<String>,
+    pub language: Option<String>,
+    pub category_ids: Option<Vec<i32>>,
+    pub notes: Option<String>,
+}
+
+/// Optional body of a submission approval.
+#[derive(Debug, Default, Deserialize)]
+pub struct SubmissionApproveRequest {
+    /// Used when the submission was sent without a reference.
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmissionRejectRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubmissionQuery {
+    pub status: Option<SubmissionStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseSubmission {
+    pub id: i32,
+    pub status: String,
+    pub content: String,
+    pub author: String,
+    pub reference: Option<String>,
+    pub language: String,
+    pub category_ids: Vec<i32>,
+    pub notes: Option<String>,
+    pub rejection_reason: Option<String>,
+    pub quote_id: Option<i32>,
+    pub translation_of: Option<i32>,
+    pub submitted_by: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmissionApproval {
+    pub submission: ResponseSubmission,
+    pub quote: ResponseQuote,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Misattribution,
+    Typo,
+    WrongReference,
+    Offensive,
+}
+
+impl ReportReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Misattribution => "misattribution",
+            ReportReason::Typo => "typo",
+            ReportReason::WrongReference => "wrong_reference",
+            ReportReason::Offensive => "offensive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Open,
+    Resolved,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Resolved => "resolved",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportRequest {
+    pub reason: ReportReason,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    pub status: Option<ReportStatus>,
+    pub reason: Option<ReportReason>,
+    pub quote_id: Option<i32>,
+}
+
+/// Resolves a report, optionally applying `edit` to the quote first.
+#[derive(Debug, Deserialize)]
+pub struct ReportResolveRequest {
+    pub edit: Option<QuoteUpdateRequest>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportDismissRequest {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResponseReport {
+    pub id: i32,
+    pub quote_id: i32,
+    pub reason: String,
+    pub details: Option<String>,
+    pub reported_by: String,
+    pub status: String,
+    pub resolution: Option<String>,
+    pub revision: Option<i32>,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportResolution {
+    pub report: ResponseReport,
+    pub quote: Option<ResponseQuote>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintIssue {
+    pub rule: &'static str,
+    pub severity: LintSeverity,
+    pub field: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    pub quote_id: i32,
+    pub language: String,
+    #[serde(flatten)]
+    pub issue: LintIssue,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LintQuery {
+    pub lang: Option<String>,
+    pub rule: Option<String>,
+    pub severity: Option<LintSeverity>,
+}
+
+/// Lint findings over every live quote. `counts` covers all findings that
+/// match the filters, `issues` only the requested page of them.
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub checked: u64,
+    pub counts: BTreeMap<String, u64>,
+    pub issues: PagedResponse<LintFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingRequest {
+    pub value: i16,
+}
+
+/// Aggregate rating of a quote, with the caller's own rating when it has one.
+#[derive(Debug, Serialize)]
+pub struct RatingSummary {
+    pub quote_id: i32,
+    pub ratings: i64,
+    pub average: Option<f64>,
+    pub score: f64,
+    pub mine: Option<i16>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PopularQuery {
+    pub lang: Option<String>,
+    pub period: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PopularQuote {
+    pub quote: ResponseQuote,
+    pub ratings: i64,
+    pub average: f64,
+    pub score: f64,
+}
+
+/// One quote in the import/export schema. Categories are given by slug or
+/// name and are created when they do not exist yet. `id` and
+/// `translation_group_id` are the exporting database's ids: on import the
+/// former is ignored and the latter is kept only when the file shares a quote
+/// with that group, otherwise it groups rows into a new translation group.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuoteRecord {
+    #[serde(default)]
+    pub id: Option<i32>,
+    pub content: String,
+    pub author: String,
+    #[serde(default)]
+    pub author_id: Option<i32>,
+    #[serde(default)]
+    pub author_record: Option<AuthorRecord>,
+    #[serde(default)]
+    pub reference: String,
+    #[serde(default)]
+    pub references: Vec<SourceRecord>,
+    pub language: String,
+    #[serde(default)]
+    pub attribution_status: Option<String>,
+    #[serde(default)]
+    pub grading_notes: Option<String>,
+    #[serde(default)]
+    pub graded_by: Option<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub translation_group_id: Option<i32>,
+    #[serde(default)]
+    pub translation_original: bool,
+    #[serde(default)]
+    pub qotd_dates: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorRecord {
+    pub name: String,
+    #[serde(default)]
+    pub era: Option<String>,
+    #[serde(default)]
+    pub birth_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub death_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceRecord {
+    #[serde(default = "default_source_kind")]
+    pub kind: String,
+    pub title: String,
+    #[serde(default)]
+    pub volume: Option<String>,
+    #[serde(default)]
+    pub chapter: Option<String>,
+    #[serde(default)]
+    pub page: Option<String>,
+    #[serde(default)]
+    pub collection: Option<String>,
+    #[serde(default)]
+    pub number: Option<String>,
+    #[serde(default)]
+    pub edition: Option<String>,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryRecord {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+}
+
+/// Leads every export: the whole JSON document, or the first NDJSON line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHeader {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub exported_at: Option<String>,
+    #[serde(default)]
+    pub categories: Vec<CategoryRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateQuery {
+    pub lang: Option<String>,
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicatePair {
+    pub language: String,
+    pub similarity: f64,
+    pub quote_ids: [i32; 2],
+    pub contents: [String; 2],
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateReport {
+    pub threshold: f64,
+    pub pairs: Vec<DuplicatePair>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    pub format: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportIssue {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub committed: bool,
+    pub rows: usize,
+    pub valid: usize,
+    pub quote_ids: Vec<i32>,
+    pub created_categories: Vec<String>,
+    pub duplicates: Vec<ImportIssue>,
+    pub warnings: Vec<ImportIssue>,
+    pub errors: Vec<ImportIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransliterateRequest {
+    pub text: String,
+    pub script: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransliterateResponse {
+    pub script: String,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeedTransliterationRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeedTransliterationResponse {
+    pub dry_run: bool,
+    pub scanned: usize,
+    pub skipped: usize,
+    pub created: Vec<SeededQuote>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeededQuote {
+    pub source_quote_id: i32,
+    pub submission_id: Option<i32>,
+    pub translation_group_id: Option<i32>,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranslationsResponse {
+    pub quote_id: i32,
+    pub translation_group_id: Option<i32>,
+    pub original_quote_id: Option<i32>,
+    pub translations: Vec<ResponseQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranslationGroupRequest {
+    pub original_quote_id: Option<i32>,
+    pub quote_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranslationGroupResponse {
+    pub id: i32,
+    pub original_quote_id: Option<i32>,
+    pub quote_ids: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CiteQuery {
+    pub style: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CitationResponse {
+    pub quote_id: i32,
+    pub style: String,
+    pub citation: String,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct KeyRequest {
     pub owner_id: String,
@@ -45,6 +849,14 @@ pub struct CacheResponse {
     pub success: bool,
 }
 
+fn default_source_kind() -> String {
+    "other".to_string()
+}
+
+fn default_direction() -> String {
+    "ltr".to_string()
+}
+
 fn default_date() -> String {
     let now: DateTime<Utc> = Utc::now();
     now.to_rfc3339()
@@ -15,6 +15,8 @@ pub enum AppError {
     RedisError(RedisError),
     SerdeError(SerdeError),
     ApiKeyError(String),
+    BadRequest(String),
+    Internal(String),
 }
 
 impl fmt::Display for AppError {
@@ -28,6 +30,8 @@ impl fmt::Display for AppError {
             AppError::RedisError(e) => write!(f, "Redis error: {}", e),
             AppError::SerdeError(e) => write!(f, "Serialization error: {}", e),
             AppError::ApiKeyError(msg) => write!(f, "Internal server error: {}", msg),
+            AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::Internal(msg) => write!(f, "Internal server error: {}", msg),
         }
     }
 }
@@ -41,14 +45,15 @@ impl ResponseError for AppError {
             | AppError::ActixError(_)
             | AppError::IOError(_)
             | AppError::RedisError(_)
-            | AppError::SerdeError(_) => HttpResponse::InternalServerError().json({
+            | AppError::SerdeError(_)
+            | AppError::Internal(_) => HttpResponse::InternalServerError().json({
                 serde_json::json!({"error": "Internal Server Error", "message": self.to_string()})
             }),
             AppError::ApiKeyError(_) => HttpResponse::Forbidden()
                 .json(serde_json::json!({"error": "Forbidden", "message": self.to_string()})),
             AppError::NotFound(_) => HttpResponse::NotFound()
                 .json(serde_json::json!({"error": "Not Found", "message": self.to_string()})),
-            AppError::SystemTimeError(_) => HttpResponse::BadRequest()
+            AppError::SystemTimeError(_) | AppError::BadRequest(_) => HttpResponse::BadRequest()
                 .json(serde_json::json!({"error": "Bad Request", "message": self.to_string()})),
         }
     }
@@ -0,0 +1,577 @@
+use crate::db::queries::pg;
+use crate::helper::admin_quotes::WriteWarnings;
+use crate::helper::export::ExportFormat;
+use crate::helper::import::ImportFormat;
+use crate::helper::transliteration::{self, UrduScript};
+use crate::helper::{self, oauth};
+use crate::models::data::{
+    ApiKeyId, AppState, AuthorMergeRequest, BulkCategoryRequest, CategoryMergeRequest,
+    CategoryRequest, CategoryUpdateRequest, DuplicateQuery, ExportQuery, ImportQuery, LintQuery,
+    PageQuery, QuoteRequest, QuoteUpdateRequest, ReportDismissRequest, ReportQuery,
+    ReportResolveRequest, SeedTransliterationRequest, SubmissionApproveRequest, SubmissionQuery,
+    SubmissionRejectRequest, SubmissionUpdateRequest, TranslationGroupRequest,
+    TransliterateRequest, TransliterateResponse,
+};
+use crate::models::errors::AppError;
+use actix_web::{
+    http::header, middleware::from_fn, web, HttpRequest, HttpResponse, HttpResponseBuilder, Scope,
+};
+use futures_util::TryStreamExt;
+use serde_json::json;
+
+/// Import files can be far larger than actix's default 256 KiB body limit.
+const IMPORT_PAYLOAD_LIMIT: usize = 32 * 1024 * 1024;
+
+pub fn admin_routes() -> Scope {
+    actix_web::web::scope("/admin")
+        .service(
+            web::resource("/quotes")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(create_quote)),
+        )
+        .service(
+            web::resource("/quotes/{id}")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::patch().to(update_quote))
+                .route(web::delete().to(delete_quote)),
+        )
+        .service(
+            web::resource("/quotes/{id}/restore")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(restore_quote)),
+        )
+        .service(
+            web::resource("/quotes/{id}/revisions")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(list_revisions)),
+        )
+        .service(
+            web::resource("/quotes/{id}/revisions/{revision}/revert")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(revert_quote)),
+        )
+        .service(
+            web::resource("/authors/merge_candidates")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(author_merge_candidates)),
+        )
+        .service(
+            web::resource("/authors/{id}/merge")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(merge_author)),
+        )
+        .service(
+            web::resource("/categories")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(create_category)),
+        )
+        .service(
+            web::resource("/categories/bulk")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(bulk_assign_categories)),
+        )
+        .service(
+            web::resource("/categories/{id}")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::patch().to(update_category))
+                .route(web::delete().to(delete_category)),
+        )
+        .service(
+            web::resource("/categories/{id}/merge")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(merge_category)),
+        )
+        .service(
+            web::resource("/duplicates")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(duplicate_report)),
+        )
+        .service(
+            web::resource("/export")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(export_quotes)),
+        )
+        .service(
+            web::resource("/import")
+                .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(import_quotes)),
+        )
+        .service(
+            web::resource("/lint")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(lint_report)),
+        )
+        .service(
+            web::resource("/reports")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(list_reports)),
+        )
+        .service(
+            web::resource("/reports/{id}/resolve")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(resolve_report)),
+        )
+        .service(
+            web::resource("/reports/{id}/dismiss")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(dismiss_report)),
+        )
+        .service(
+            web::resource("/submissions")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::get().to(list_submissions)),
+        )
+        .service(
+            web::resource("/submissions/{id}")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::patch().to(update_submission)),
+        )
+        .service(
+            web::resource("/submissions/{id}/approve")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(approve_submission)),
+        )
+        .service(
+            web::resource("/submissions/{id}/reject")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(reject_submission)),
+        )
+        .service(
+            web::resource("/translation_groups")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(create_translation_group)),
+        )
+        .service(
+            web::resource("/translation_groups/{id}")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::delete().to(delete_translation_group)),
+        )
+        .service(
+            web::resource("/transliterate")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(transliterate)),
+        )
+        .service(
+            web::resource("/transliterate/seed_ur_arab")
+                .wrap(from_fn(oauth::admin_check))
+                .route(web::post().to(seed_urdu_script_quotes)),
+        )
+}
+
+/// The verified key id that `admin_check` stored on the request, recorded as
+/// the author of quote revisions.
+fn changed_by(key: &Option<web::ReqData<ApiKeyId>>) -> Option<&str> {
+    oauth::verified_key_id(key).ok()
+}
+
+async fn create_quote(
+    app_state: web::Data<AppState>,
+    req_body: web::Json<QuoteRequest>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let (response, warnings) =
+        helper::admin_quotes::create_quote(app_state.db.as_ref(), &req_body, changed_by(&key))
+            .await?;
+
+    Ok(with_warnings(HttpResponse::Created(), &warnings).json(response))
+}
+
+/// Lists near-duplicates and the ids of lint rules a written quote tripped,
+/// so clients can surface them without a change to the response body.
+fn with_warnings(
+    mut builder: HttpResponseBuilder,
+    warnings: &WriteWarnings,
+) -> HttpResponseBuilder {
+    if !warnings.possible_duplicates.is_empty() {
+        let ids: Vec<String> = warnings
+            .possible_duplicates
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        builder.insert_header(("X-Possible-Duplicates", ids.join(",")));
+    }
+    if !warnings.lint.is_empty() {
+        let mut rules: Vec<&str> = warnings.lint.iter().map(|issue| issue.rule).collect();
+        rules.sort();
+        rules.dedup();
+        builder.insert_header(("X-Lint-Warnings", rules.join(",")));
+    }
+    builder
+}
+
+async fn update_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    req_body: web::Json<QuoteUpdateRequest>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let (response, warnings) = helper::admin_quotes::update_quote(
+        app_state.db.as_ref(),
+        app_state.redis_client.as_ref(),
+        path.into_inner(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(with_warnings(HttpResponse::Ok(), &warnings).json(response))
+}
+
+async fn delete_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    helper::admin_quotes::delete_quote(
+        app_state.db.as_ref(),
+        app_state.redis_client.as_ref(),
+        path.into_inner(),
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(json!({ "message": "Quote deleted successfully" })))
+}
+
+async fn restore_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::admin_quotes::restore_quote(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn list_revisions(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::admin_quotes::list_revisions(app_state.db.as_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn revert_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<(i32, i32)>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let (quote_id, revision) = path.into_inner();
+    let response = helper::admin_quotes::revert_quote(
+        app_state.db.as_ref(),
+        app_state.redis_client.as_ref(),
+        quote_id,
+        revision,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn create_category(
+    app_state: web::Data<AppState>,
+    req_body: web::Json<CategoryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::categories::create_category(app_state.db.as_ref(), &req_body).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+async fn update_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    req_body: web::Json<CategoryUpdateRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::categories::update_category(app_state.db.as_ref(), path.into_inner(), &req_body)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn delete_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    pg::categories::delete_category(app_state.db.as_ref(), path.into_inner(), changed_by(&key))
+        .await?;
+    Ok(HttpResponse::Ok().json(json!({ "message": "Category deleted successfully" })))
+}
+
+async fn author_merge_candidates(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let response = helper::authors::merge_candidates(app_state.db.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn merge_author(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    req_body: web::Json<AuthorMergeRequest>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::authors::merge_author(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn merge_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    req_body: web::Json<CategoryMergeRequest>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::categories::merge_category(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn bulk_assign_categories(
+    app_state: web::Data<AppState>,
+    req_body: web::Json<BulkCategoryRequest>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::categories::bulk_assign_categories(
+        app_state.db.as_ref(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn duplicate_report(
+    app_state: web::Data<AppState>,
+    query: web::Query<DuplicateQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::dedup::duplicate_report(
+        app_state.db.as_ref(),
+        query.lang.as_deref(),
+        query.threshold,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn export_quotes(
+    app_state: web::Data<AppState>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    let format = match &query.format {
+        Some(format) => format.parse::<ExportFormat>()?,
+        None => ExportFormat::Json,
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"quotes.{}\"", format.extension()),
+        ))
+        .streaming(
+            helper::export::export_stream(app_state.db.clone(), format)
+                .map_err(actix_web::Error::from),
+        ))
+}
+
+async fn import_quotes(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ImportQuery>,
+    body: web::Bytes,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let format = match &query.format {
+        Some(format) => format.parse::<ImportFormat>()?,
+        None => ImportFormat::from_content_type(content_type).ok_or_else(|| {
+            AppError::BadRequest(
+                "Pass ?format=csv|json|yaml or a matching Content-Type".to_string(),
+            )
+        })?,
+    };
+
+    let rows = helper::import::parse_records(format, &body)?;
+    let report =
+        helper::import::import_quotes(app_state.db.as_ref(), rows, query.dry_run, changed_by(&key))
+            .await?;
+
+    match report.errors.is_empty() {
+        true => Ok(HttpResponse::Ok().json(report)),
+        false => Ok(HttpResponse::UnprocessableEntity().json(report)),
+    }
+}
+
+async fn lint_report(
+    app_state: web::Data<AppState>,
+    query: web::Query<LintQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::lint::lint_report(app_state.db.as_ref(), &query, &page).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn list_reports(
+    app_state: web::Data<AppState>,
+    query: web::Query<ReportQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::reports::list_reports(app_state.db.as_ref(), &query, &page).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn resolve_report(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<ReportResolveRequest>,
+) -> Result<HttpResponse, AppError> {
+    let (response, warnings) = helper::reports::resolve_report(
+        app_state.db.as_ref(),
+        app_state.redis_client.as_ref(),
+        path.into_inner(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(with_warnings(HttpResponse::Ok(), &warnings).json(response))
+}
+
+async fn dismiss_report(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<ReportDismissRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::reports::dismiss_report(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        &req_body,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn list_submissions(
+    app_state: web::Data<AppState>,
+    query: web::Query<SubmissionQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::submissions::list_submissions(app_state.db.as_ref(), None, &query, &page).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn update_submission(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    req_body: web::Json<SubmissionUpdateRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::submissions::update_submission(app_state.db.as_ref(), path.into_inner(), &req_body)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn approve_submission(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: Option<web::Json<SubmissionApproveRequest>>,
+) -> Result<HttpResponse, AppError> {
+    let req_body = req_body.map(web::Json::into_inner).unwrap_or_default();
+    let (response, warnings) = helper::submissions::approve_submission(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        req_body.reference.as_deref(),
+        changed_by(&key),
+    )
+    .await?;
+    Ok(with_warnings(HttpResponse::Ok(), &warnings).json(response))
+}
+
+async fn reject_submission(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<SubmissionRejectRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::submissions::reject_submission(
+        app_state.db.as_ref(),
+        path.into_inner(),
+        &req_body.reason,
+        changed_by(&key),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn create_translation_group(
+    app_state: web::Data<AppState>,
+    req_body: web::Json<TranslationGroupRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = pg::translations::create_translation_group(
+        app_state.db.as_ref(),
+        req_body.original_quote_id,
+        &req_body.quote_ids,
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+async fn delete_translation_group(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    pg::translations::delete_translation_group(app_state.db.as_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(json!({ "message": "Translation group deleted successfully" })))
+}
+
+async fn transliterate(
+    req_body: web::Json<TransliterateRequest>,
+) -> Result<HttpResponse, AppError> {
+    let script = UrduScript::parse(&req_body.script)?;
+    let text = match script {
+        UrduScript::Arab => transliteration::roman_to_urdu(&req_body.text),
+        UrduScript::Latn => transliteration::urdu_to_roman(&req_body.text),
+    };
+
+    Ok(HttpResponse::Ok().json(TransliterateResponse {
+        script: req_body.script.to_ascii_lowercase(),
+        text,
+    }))
+}
+
+async fn seed_urdu_script_quotes(
+    app_state: web::Data<AppState>,
+    req_body: web::Json<SeedTransliterationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = pg::translations::seed_converted_quotes(
+        app_state.db.as_ref(),
+        UrduScript::Latn.language_code(),
+        UrduScript::Arab.language_code(),
+        transliteration::roman_to_urdu,
+        transliteration::SEED_SUBMITTER,
+        req_body.dry_run,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,39 @@
+use crate::db::queries::pg;
+use crate::models::data::{AppState, PageQuery};
+use crate::models::errors::AppError;
+use actix_web::{web, HttpResponse, Scope};
+
+pub fn authors_routes() -> Scope {
+    actix_web::web::scope("/authors")
+        .service(web::resource("").route(web::get().to(get_authors)))
+        .service(web::resource("/{id}").route(web::get().to(get_author)))
+        .service(web::resource("/{id}/quotes").route(web::get().to(get_author_quotes)))
+}
+
+async fn get_authors(
+    app_state: web::Data<AppState>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response = pg::authors::fetch_authors(app_state.db.as_ref(), &page).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn get_author(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        pg::authors::fetch_author_by_id(app_state.db.as_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn get_author_quotes(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        pg::authors::fetch_quotes_by_author(app_state.db.as_ref(), path.into_inner(), &page)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
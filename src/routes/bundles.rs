@@ -0,0 +1,55 @@
+use crate::helper;
+use crate::models::data::AppState;
+use crate::models::errors::AppError;
+use actix_web::http::header::{self, ETag, EntityTag};
+use actix_web::{web, HttpRequest, HttpResponse, Scope};
+
+pub fn bundles_routes() -> Scope {
+    actix_web::web::scope("/bundles")
+        .service(web::resource("/{lang}").route(web::get().to(get_bundle)))
+}
+
+async fn get_bundle(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let language = helper::languages::resolve_language(db_conn, &path).await?;
+    let (meta, body) =
+        helper::bundles::get_bundle(db_conn, app_state.redis_client.as_ref(), &language.code)
+            .await?;
+
+    let etag = EntityTag::new_strong(meta.sha256.clone());
+    let not_modified = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim().trim_matches('"') == meta.sha256)
+        });
+
+    let mut response = match not_modified {
+        true => HttpResponse::NotModified(),
+        false => HttpResponse::Ok(),
+    };
+    response
+        .insert_header(ETag(etag))
+        .insert_header(("X-Bundle-Cursor", meta.cursor.to_string()))
+        .insert_header(("X-Bundle-Count", meta.count.to_string()))
+        .insert_header(("X-Checksum-SHA256", meta.sha256.clone()));
+
+    if not_modified {
+        return Ok(response.finish());
+    }
+
+    Ok(response
+        .content_type("application/gzip")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"quotes-{}.json.gz\"", meta.language),
+        ))
+        .body(body))
+}
@@ -1,10 +1,10 @@
+use crate::db::queries::pg;
 use crate::helper::oauth;
 use crate::models::data::AppState;
 use crate::models::errors::AppError;
-use crate::utils;
 use crate::{helper, models::data::CacheResponse};
 use actix_web::{middleware::from_fn, web, HttpResponse, Scope};
-use futures_util::future;
+use log::error;
 
 pub fn cache_routes() -> Scope {
     actix_web::web::scope("/cache_qotd").service(
@@ -15,18 +15,20 @@ pub fn cache_routes() -> Scope {
 }
 
 async fn cache_qotd(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
-    let tasks = utils::constants::Language::variants()
-        .into_iter()
-        .map(|lang| {
-            helper::quotes::update_qotd_cache_for_language(
-                app_state.db.as_ref(),
-                app_state.redis_client.as_ref(),
-                lang.as_str(),
-            )
-        })
-        .collect::<Vec<_>>();
+    let languages = pg::languages::fetch_qotd_languages(app_state.db.as_ref()).await?;
 
-    future::join_all(tasks).await;
+    // Sequential on purpose: a single rollover refreshes every language.
+    for lang in languages {
+        if let Err(e) = helper::quotes::update_qotd_cache_for_language(
+            app_state.db.as_ref(),
+            app_state.redis_client.as_ref(),
+            &lang.code,
+        )
+        .await
+        {
+            error!("Failed to refresh QOTD cache for '{}': {}", lang.code, e);
+        }
+    }
 
     let value = CacheResponse {
         message: "Operation completed successfully".to_string(),
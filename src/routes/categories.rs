@@ -0,0 +1,48 @@
+use crate::helper;
+use crate::models::data::{AppState, LanguageQuery, PageQuery};
+use crate::models::errors::AppError;
+use actix_web::{web, HttpResponse, Scope};
+
+pub fn categories_routes() -> Scope {
+    actix_web::web::scope("/categories")
+        .service(web::resource("").route(web::get().to(get_categories)))
+        .service(web::resource("/{slug}/quotes").route(web::get().to(get_category_quotes)))
+}
+
+async fn get_categories(
+    app_state: web::Data<AppState>,
+    query: web::Query<LanguageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let language = match query.lang.as_deref() {
+        Some(code) => Some(
+            helper::languages::resolve_language(db_conn, code)
+                .await?
+                .code,
+        ),
+        None => None,
+    };
+    let response = helper::categories::list_categories(db_conn, language.as_deref()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn get_category_quotes(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<LanguageQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let language = match query.lang.as_deref() {
+        Some(code) => Some(
+            helper::languages::resolve_language(db_conn, code)
+                .await?
+                .code,
+        ),
+        None => None,
+    };
+    let response =
+        helper::categories::fetch_category_quotes(db_conn, &path, language.as_deref(), &page)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
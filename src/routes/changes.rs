@@ -0,0 +1,32 @@
+use crate::helper;
+use crate::models::data::{AppState, ChangesQuery};
+use crate::models::errors::AppError;
+use actix_web::{web, HttpResponse, Scope};
+
+pub fn changes_routes() -> Scope {
+    actix_web::web::scope("/changes").service(web::resource("").route(web::get().to(get_changes)))
+}
+
+async fn get_changes(
+    app_state: web::Data<AppState>,
+    query: web::Query<ChangesQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let language = match query.lang.as_deref() {
+        Some(code) => Some(
+            helper::languages::resolve_language(db_conn, code)
+                .await?
+                .code,
+        ),
+        None => None,
+    };
+
+    let response = helper::changes::fetch_changes(
+        db_conn,
+        query.since.unwrap_or(0),
+        language.as_deref(),
+        query.limit,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,14 @@
+use crate::helper;
+use crate::models::data::AppState;
+use crate::models::errors::AppError;
+use actix_web::{web, HttpResponse, Scope};
+
+pub fn languages_routes() -> Scope {
+    actix_web::web::scope("/languages")
+        .service(web::resource("").route(web::get().to(get_languages)))
+}
+
+async fn get_languages(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let response = helper::languages::list_languages(app_state.db.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
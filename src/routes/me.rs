@@ -0,0 +1,92 @@
+use crate::helper::favorites::FavoriteOwner;
+use crate::helper::{self, oauth};
+use crate::models::data::{ApiKeyId, AppState, PageQuery};
+use crate::models::errors::AppError;
+use actix_web::{middleware::from_fn, web, HttpRequest, HttpResponse, Scope};
+use serde_json::json;
+
+pub fn me_routes() -> Scope {
+    actix_web::web::scope("/me")
+        .service(
+            web::resource("/favorites")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::get().to(list_favorites)),
+        )
+        .service(
+            web::resource("/favorites/random")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::get().to(random_favorite)),
+        )
+        .service(
+            web::resource("/favorites/{quote_id}")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::put().to(add_favorite))
+                .route(web::delete().to(remove_favorite)),
+        )
+}
+
+fn owner<'a>(
+    req: &'a HttpRequest,
+    key: &'a Option<web::ReqData<ApiKeyId>>,
+) -> Result<FavoriteOwner<'a>, AppError> {
+    Ok(FavoriteOwner {
+        key_id: oauth::verified_key_id(key)?,
+        end_user_id: helper::favorites::end_user_id(req.headers())?,
+    })
+}
+
+async fn list_favorites(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    key: Option<web::ReqData<ApiKeyId>>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::favorites::list_favorites(app_state.db.as_ref(), &owner(&req, &key)?, &page)
+            .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn random_favorite(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        helper::favorites::random_favorite(app_state.db.as_ref(), &owner(&req, &key)?).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn add_favorite(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    key: Option<web::ReqData<ApiKeyId>>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let added = helper::favorites::add_favorite(
+        app_state.db.as_ref(),
+        &owner(&req, &key)?,
+        path.into_inner(),
+    )
+    .await?;
+
+    match added {
+        true => Ok(HttpResponse::Created().json(json!({ "message": "Quote added to favorites" }))),
+        false => Ok(HttpResponse::Ok().json(json!({ "message": "Quote is already a favorite" }))),
+    }
+}
+
+async fn remove_favorite(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    key: Option<web::ReqData<ApiKeyId>>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    helper::favorites::remove_favorite(
+        app_state.db.as_ref(),
+        &owner(&req, &key)?,
+        path.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(json!({ "message": "Quote removed from favorites" })))
+}
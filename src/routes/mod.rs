@@ -1,14 +1,31 @@
+pub mod admin;
+pub mod authors;
+pub mod bundles;
 pub mod cache;
+pub mod categories;
+pub mod changes;
 pub mod health;
+pub mod languages;
+pub mod me;
 pub mod oauth;
 pub mod qotd;
 pub mod quotes;
+pub mod submissions;
 use actix_web::web;
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(health::health_check)
         .service(quotes::quotes_routes())
+        .service(quotes::quote_routes())
         .service(qotd::qotd_routes())
         .service(oauth::oauth_routes())
-        .service(cache::cache_routes());
+        .service(cache::cache_routes())
+        .service(authors::authors_routes())
+        .service(admin::admin_routes())
+        .service(languages::languages_routes())
+        .service(categories::categories_routes())
+        .service(changes::changes_routes())
+        .service(bundles::bundles_routes())
+        .service(submissions::submissions_routes())
+        .service(me::me_routes());
 }
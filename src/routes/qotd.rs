@@ -1,44 +1,58 @@
 use crate::helper;
 use crate::helper::oauth;
-use crate::models::data::AppState;
+use crate::models::data::{AppState, DisplayQuery};
 use crate::models::errors::AppError;
-use crate::utils;
-use actix_web::{middleware::from_fn, web, HttpResponse, Scope};
+use actix_web::{http::header, middleware::from_fn, web, HttpRequest, HttpResponse, Scope};
 
 pub fn qotd_routes() -> Scope {
     actix_web::web::scope("/qotd")
         .service(
-            web::resource(utils::constants::Language::English.as_str())
+            web::resource("")
                 .wrap(from_fn(oauth::rate_limit))
-                .route(web::get().to(get_qotd_english)),
+                .route(web::get().to(get_negotiated_qotd)),
         )
         .service(
-            web::resource(utils::constants::Language::RomanUrdu.as_str())
+            web::resource("/{lang}")
                 .wrap(from_fn(oauth::rate_limit))
-                .route(web::get().to(get_qotd_roman_urdu)),
+                .route(web::get().to(get_qotd_by_language)),
         )
 }
 
-async fn get_qotd_english(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+async fn qotd_response(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    language: Option<&str>,
+    query: web::Query<DisplayQuery>,
+) -> Result<HttpResponse, AppError> {
     let db_conn = app_state.db.as_ref();
     let redis_client = app_state.redis_client.as_ref();
-    let response = helper::quotes::get_qotd_by_language(
+    let chain = helper::languages::request_language_chain(
         db_conn,
-        redis_client,
-        utils::constants::Language::English.as_str(),
+        language,
+        helper::languages::accept_language(req.headers()),
     )
     .await?;
-    Ok(HttpResponse::Ok().json(response))
+    let response = helper::quotes::qotd_with_fallback(db_conn, redis_client, &chain).await?;
+    let content_language = response.language.clone();
+    let payload = helper::quotes::build_display_payload(db_conn, response, &query).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_LANGUAGE, content_language))
+        .json(payload))
 }
 
-async fn get_qotd_roman_urdu(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
-    let db_conn = app_state.db.as_ref();
-    let redis_client = app_state.redis_client.as_ref();
-    let response = helper::quotes::get_qotd_by_language(
-        db_conn,
-        redis_client,
-        utils::constants::Language::RomanUrdu.as_str(),
-    )
-    .await?;
-    Ok(HttpResponse::Ok().json(response))
+async fn get_negotiated_qotd(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<DisplayQuery>,
+) -> Result<HttpResponse, AppError> {
+    qotd_response(app_state, req, None, query).await
+}
+
+async fn get_qotd_by_language(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<DisplayQuery>,
+) -> Result<HttpResponse, AppError> {
+    qotd_response(app_state, req, Some(&path), query).await
 }
@@ -1,42 +1,238 @@
 use crate::db::queries::pg;
-use crate::models::data::AppState;
+use crate::helper;
+use crate::helper::arabic;
+use crate::helper::attribution;
+use crate::helper::citation::{self, CitationStyle};
+use crate::helper::languages;
+use crate::helper::oauth;
+use crate::helper::ratings::Period;
+use crate::models::data::{
+    ApiKeyId, AppState, CitationResponse, CiteQuery, DisplayQuery, PageQuery, PopularQuery,
+    RandomQuoteQuery, RatingRequest, ReportRequest, SearchQuery,
+};
 use crate::models::errors::AppError;
-use crate::utils;
-use actix_web::{web, HttpResponse, Scope};
+use actix_web::{http::header, middleware::from_fn, web, HttpRequest, HttpResponse, Scope};
 
 pub fn quotes_routes() -> Scope {
     actix_web::web::scope("/random_quote")
+        .service(web::resource("").route(web::get().to(get_negotiated_quote)))
+        .service(web::resource("/{lang}").route(web::get().to(get_quotes_by_language)))
+}
+
+pub fn quote_routes() -> Scope {
+    actix_web::web::scope("/quotes")
+        .service(web::resource("/search").route(web::get().to(search_quotes)))
+        .service(web::resource("/popular").route(web::get().to(popular_quotes)))
+        .service(web::resource("/{id}").route(web::get().to(get_quote)))
+        .service(web::resource("/{id}/cite").route(web::get().to(cite_quote)))
+        .service(web::resource("/{id}/translations").route(web::get().to(get_translations)))
         .service(
-            web::resource(utils::constants::Language::English.as_str())
-                .route(web::get().to(get_quote_english)),
-        )
-        .service(
-            web::resource(utils::constants::Language::Arabic.as_str())
-                .route(web::get().to(get_quote_arabic)),
+            web::resource("/{id}/rating")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::put().to(rate_quote))
+                .route(web::delete().to(remove_rating)),
         )
         .service(
-            web::resource(utils::constants::Language::RomanUrdu.as_str())
-                .route(web::get().to(get_quote_roman_urdu)),
+            web::resource("/{id}/reports")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::post().to(report_quote)),
         )
 }
 
+async fn random_quote_response(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    language: Option<&str>,
+    query: web::Query<RandomQuoteQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let chain = languages::request_language_chain(
+        db_conn,
+        language,
+        languages::accept_language(req.headers()),
+    )
+    .await?;
+    let min_grade = query
+        .min_grade
+        .as_deref()
+        .map(attribution::parse_attribution_status)
+        .transpose()?;
+    let response = helper::quotes::random_quote_with_fallback(
+        db_conn,
+        &chain,
+        min_grade.as_ref(),
+        query.weighted,
+    )
+    .await?;
+    let content_language = response.language.clone();
+    let payload = helper::quotes::build_display_payload(db_conn, response, &query.display).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_LANGUAGE, content_language))
+        .json(payload))
+}
+
+async fn get_negotiated_quote(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<RandomQuoteQuery>,
+) -> Result<HttpResponse, AppError> {
+    random_quote_response(app_state, req, None, query).await
+}
+
 async fn get_quotes_by_language(
     app_state: web::Data<AppState>,
-    language: &str,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<RandomQuoteQuery>,
+) -> Result<HttpResponse, AppError> {
+    random_quote_response(app_state, req, Some(&path), query).await
+}
+
+async fn search_quotes(
+    app_state: web::Data<AppState>,
+    query: web::Query<SearchQuery>,
+    page: web::Query<PageQuery>,
 ) -> Result<HttpResponse, AppError> {
     let db_conn = app_state.db.as_ref();
-    let response = pg::fetch_random_quote_by_language(db_conn, language).await?;
+    let normalized = arabic::normalize(&query.q);
+    if normalized.is_empty() {
+        return Err(AppError::BadRequest(
+            "Search query must not be empty".to_string(),
+        ));
+    }
+
+    let language = match query.lang.as_deref() {
+        Some(code) => Some(languages::resolve_language(db_conn, code).await?.code),
+        None => None,
+    };
+
+    let response = pg::search_quotes(db_conn, &normalized, language.as_deref(), &page).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
-async fn get_quote_english(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
-    get_quotes_by_language(app_state, utils::constants::Language::English.as_str()).await
+async fn get_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    query: web::Query<DisplayQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    let response = pg::fetch_quote_by_id(db_conn, path.into_inner()).await?;
+    let payload = helper::quotes::build_display_payload(db_conn, response, &query).await?;
+    Ok(HttpResponse::Ok().json(payload))
 }
 
-async fn get_quote_arabic(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
-    get_quotes_by_language(app_state, utils::constants::Language::Arabic.as_str()).await
+async fn cite_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    query: web::Query<CiteQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let style = match &query.style {
+        Some(style) => style.parse::<CitationStyle>()?,
+        None => CitationStyle::from_accept(accept).unwrap_or(CitationStyle::Apa),
+    };
+
+    let quote = pg::fetch_quote_by_id(app_state.db.as_ref(), path.into_inner()).await?;
+    let citation = citation::render_citation(&quote, style);
+
+    if accept.contains("application/json") {
+        return Ok(HttpResponse::Ok().json(CitationResponse {
+            quote_id: quote.id,
+            style: style.as_str().to_string(),
+            citation,
+        }));
+    }
+
+    let content_type = if accept.contains("text/plain") {
+        "text/plain; charset=utf-8"
+    } else {
+        style.content_type()
+    };
+
+    Ok(HttpResponse::Ok().content_type(content_type).body(citation))
+}
+
+async fn get_translations(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let response =
+        pg::translations::fetch_translations(app_state.db.as_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn report_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<ReportRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::reports::report_quote(
+        app_state.db.as_ref(),
+        app_state.redis_client.as_ref(),
+        oauth::verified_key_id(&key)?,
+        path.into_inner(),
+        &req_body,
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+async fn popular_quotes(
+    app_state: web::Data<AppState>,
+    query: web::Query<PopularQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let db_conn = app_state.db.as_ref();
+    // `?lang=` and `?period=` without a value mean no filter.
+    let language = match query.lang.as_deref().map(str::trim) {
+        Some(code) if !code.is_empty() => {
+            Some(languages::resolve_language(db_conn, code).await?.code)
+        }
+        _ => None,
+    };
+    let period = match query.period.as_deref().map(str::trim) {
+        Some(period) if !period.is_empty() => period.parse::<Period>()?,
+        _ => Period::All,
+    };
+
+    let response =
+        helper::ratings::popular_quotes(db_conn, language.as_deref(), period, &page).await?;
+    Ok(HttpResponse::Ok().json(response))
 }
 
-async fn get_quote_roman_urdu(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
-    get_quotes_by_language(app_state, utils::constants::Language::RomanUrdu.as_str()).await
+async fn rate_quote(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<RatingRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::ratings::rate_quote(
+        app_state.db.as_ref(),
+        oauth::verified_key_id(&key)?,
+        path.into_inner(),
+        req_body.value,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn remove_rating(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    key: Option<web::ReqData<ApiKeyId>>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::ratings::remove_rating(
+        app_state.db.as_ref(),
+        oauth::verified_key_id(&key)?,
+        path.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
 }
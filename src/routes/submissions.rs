@@ -0,0 +1,48 @@
+use crate::helper::{self, oauth};
+use crate::models::data::{ApiKeyId, AppState, PageQuery, SubmissionQuery, SubmissionRequest};
+use crate::models::errors::AppError;
+use actix_web::{middleware::from_fn, web, HttpResponse, Scope};
+
+pub fn submissions_routes() -> Scope {
+    actix_web::web::scope("/submissions")
+        .service(
+            web::resource("")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::post().to(create_submission)),
+        )
+        .service(
+            web::resource("/mine")
+                .wrap(from_fn(oauth::rate_limit))
+                .route(web::get().to(my_submissions)),
+        )
+}
+
+async fn create_submission(
+    app_state: web::Data<AppState>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    req_body: web::Json<SubmissionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::submissions::submit(
+        app_state.db.as_ref(),
+        oauth::verified_key_id(&key)?,
+        &req_body,
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+async fn my_submissions(
+    app_state: web::Data<AppState>,
+    key: Option<web::ReqData<ApiKeyId>>,
+    query: web::Query<SubmissionQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let response = helper::submissions::list_submissions(
+        app_state.db.as_ref(),
+        Some(oauth::verified_key_id(&key)?),
+        &query,
+        &page,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -1,23 +1,10 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Language {
-    English,
-    RomanUrdu,
-    Arabic,
-}
+/// Language served when negotiation finds no match, and the last link of
+/// every fallback chain.
+pub const DEFAULT_LANGUAGE: &str = "en";
 
-impl Language {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Language::English => "en",
-            Language::RomanUrdu => "ur-Latn",
-            Language::Arabic => "ar",
-        }
-    }
-
-    pub const fn variants() -> &'static [Language] {
-        &[Language::English, Language::RomanUrdu]
-    }
-}
+/// Version of the import/export record schema. Bump it whenever a field
+/// changes meaning; imports reject newer versions.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum APILimit {
@@ -38,15 +25,61 @@ impl APILimit {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RequestLimit {
-    RPS,
+    Rps,
     BurstSize,
 }
 
 impl RequestLimit {
     pub fn as_u64(&self) -> u64 {
         match self {
-            RequestLimit::RPS => 2,
+            RequestLimit::Rps => 2,
             RequestLimit::BurstSize => 3,
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFeed {
+    DefaultLimit,
+    MaxLimit,
+}
+
+impl ChangeFeed {
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            ChangeFeed::DefaultLimit => 500,
+            ChangeFeed::MaxLimit => 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pagination {
+    DefaultPerPage,
+    MaxPerPage,
+}
+
+impl Pagination {
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            Pagination::DefaultPerPage => 20,
+            Pagination::MaxPerPage => 100,
+        }
+    }
+}
+
+/// Correction reports a single API key may file per window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportLimit {
+    PerWindow,
+    WindowSeconds,
+}
+
+impl ReportLimit {
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            ReportLimit::PerWindow => 10,
+            ReportLimit::WindowSeconds => 3600,
+        }
+    }
+}